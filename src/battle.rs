@@ -0,0 +1,237 @@
+use core::cmp::Reverse;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    ailment::Ailment,
+    moves::{Accuracy, Move},
+    pokemon::{
+        stat::{BaseStat, StatType},
+        OwnedRefPokemon,
+    },
+};
+
+mod action;
+pub use action::*;
+
+/// Sorts the pokemon using moves this turn into the order they move in.
+/// Moves with a higher priority go first, then faster pokemon. Paralysis halves a pokemon's speed,
+/// and pokemon with the same priority and speed are ordered randomly.
+pub fn order_turn(actions: &mut [(&OwnedRefPokemon, &Move)], random: &mut impl Rng) {
+    actions.shuffle(random);
+    actions.sort_by_key(|(pokemon, m)| {
+        let speed = pokemon.stat(StatType::Speed);
+        let speed = match pokemon.ailment.map(|live| live.ailment) {
+            Some(Ailment::Paralysis) => speed / 2,
+            _ => speed,
+        };
+        Reverse((m.priority, speed))
+    });
+}
+
+/// The damage multiplier of a critical hit.
+/// Critical hits should also ignore the attacker's negative attack stages and the defender's positive defense stages.
+pub const fn crit_multiplier() -> f32 {
+    1.5
+}
+
+/// Rolls whether a move with this critical hit stage lands a critical hit (Gen 7 onwards).
+/// The chance is 1/24 at stage 0, 1/8 at stage 1, 1/2 at stage 2 and certain at stage 3 or above.
+pub fn roll_crit(random: &mut impl Rng, crit_stage: u8) -> bool {
+    let odds = match crit_stage {
+        0 => 24,
+        1 => 8,
+        2 => 2,
+        _ => return true,
+    };
+    random.gen_range(0..odds) == 0
+}
+
+/// A stat stage, from -6 to +6.
+pub type Stage = i8;
+
+/// The multiplier of an accuracy or evasion stage (Gen 3 onwards).
+/// Stages change the chance of hitting by thirds, from 3/9 at -6 to 9/3 at +6.
+/// Evasion stages should be subtracted from accuracy stages before being passed in.
+pub fn accuracy_stage_multiplier(stage: Stage) -> f32 {
+    let stage = stage.clamp(-6, 6) as f32;
+    match stage < 0.0 {
+        true => 3.0 / (3.0 - stage),
+        false => (3.0 + stage) / 3.0,
+    }
+}
+
+/// Rolls whether a pokemon escapes a wild battle (Gen 3 formula).
+/// A faster pokemon always escapes, and a slower one's chance increases with each attempt.
+pub fn escape_chance(
+    own_speed: BaseStat,
+    enemy_speed: BaseStat,
+    attempts: u8,
+    random: &mut impl Rng,
+) -> bool {
+    if own_speed >= enemy_speed {
+        return true;
+    }
+    let enemy = (enemy_speed / 4) % 256;
+    if enemy == 0 {
+        return true;
+    }
+    let odds = own_speed as u32 * 32 / enemy as u32 + 30 * attempts as u32;
+    odds > 255 || random.gen_range(0..256) < odds
+}
+
+/// Rolls whether a move with this accuracy hits, after the attacker's accuracy stage and the defender's evasion stage.
+/// Moves without an accuracy always hit.
+pub fn check_accuracy(
+    accuracy: Option<Accuracy>,
+    accuracy_stage: Stage,
+    evasion_stage: Stage,
+    random: &mut impl Rng,
+) -> bool {
+    match accuracy {
+        Some(accuracy) => {
+            let stage = accuracy_stage.saturating_sub(evasion_stage);
+            let chance = accuracy as f32 * accuracy_stage_multiplier(stage);
+            (random.gen_range(0..100) as f32) < chance
+        }
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ailment::LiveAilment,
+        moves::MoveCategory,
+        testing::{id, move_with, Dexes, TestRng, BULBASAUR, PIDGEY},
+        types::PokemonType,
+    };
+
+    #[test]
+    fn faster_pokemon_always_escape() {
+        let mut random = TestRng::new(1);
+        assert!((0..1000).all(|_| escape_chance(100, 20, 0, &mut random)));
+        assert!((0..1000).all(|_| escape_chance(50, 50, 0, &mut random)));
+    }
+
+    #[test]
+    fn escape_odds_increase_with_attempts() {
+        let mut random = TestRng::new(1);
+        let mut escapes = |attempts| {
+            (0..1000)
+                .filter(|_| escape_chance(20, 200, attempts, &mut random))
+                .count()
+        };
+        let (first, second, third) = (escapes(0), escapes(1), escapes(2));
+        assert!(first < second && second < third, "{} {} {}", first, second, third);
+        // the odds go over 255 after enough attempts
+        assert_eq!(escapes(9), 1000);
+    }
+
+    #[test]
+    fn accuracy_stages() {
+        assert!((accuracy_stage_multiplier(0) - 1.0).abs() < f32::EPSILON);
+        assert!((accuracy_stage_multiplier(6) - 3.0).abs() < f32::EPSILON);
+        assert!((accuracy_stage_multiplier(-6) - 1.0 / 3.0).abs() < f32::EPSILON);
+        assert!((accuracy_stage_multiplier(1) - 4.0 / 3.0).abs() < f32::EPSILON);
+        assert!((accuracy_stage_multiplier(-1) - 0.75).abs() < f32::EPSILON);
+        // stages past 6 are clamped
+        assert!((accuracy_stage_multiplier(12) - 3.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn accuracy_and_evasion() {
+        let mut random = TestRng::new(1);
+        // +6 accuracy against -6 evasion is still +6
+        assert!((0..1000).all(|_| check_accuracy(Some(50), 6, -6, &mut random)));
+        assert!((0..1000).all(|_| check_accuracy(None, -6, 6, &mut random)));
+
+        let hits = (0..1000)
+            .filter(|_| check_accuracy(Some(90), 0, 6, &mut random))
+            .count();
+        // 90 accuracy against +6 evasion hits 30% of the time
+        assert!((200..400).contains(&hits), "{}", hits);
+    }
+
+    #[test]
+    fn stage_3_always_crits() {
+        for seed in 0..100 {
+            let mut random = TestRng::new(seed);
+            assert!((0..100).all(|_| roll_crit(&mut random, 3)));
+            assert!(roll_crit(&mut random, u8::MAX));
+        }
+    }
+
+    #[test]
+    fn crit_odds_by_stage() {
+        let mut random = TestRng::new(1);
+        let mut crits = |stage| (0..2400).filter(|_| roll_crit(&mut random, stage)).count();
+        // 1/24, 1/8 and 1/2 of 2400 rolls
+        assert!((50..150).contains(&crits(0)));
+        assert!((200..400).contains(&crits(1)));
+        assert!((1000..1400).contains(&crits(2)));
+        assert!((crit_multiplier() - 1.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn move_crit_stage_is_capped() {
+        let tackle = move_with("tackle", PokemonType::Normal, MoveCategory::Physical, Some(40), Some(100), 35);
+        assert_eq!(tackle.crit_stage(), 0);
+        let m = Move { crit_rate: 5, ..tackle };
+        assert_eq!(m.crit_stage(), 3);
+    }
+
+    #[test]
+    fn priority_goes_first() {
+        let dexes = Dexes::new();
+        let pidgey = dexes.owned(PIDGEY, 50);
+        let bulbasaur = dexes.owned(BULBASAUR, 50);
+        assert!(pidgey.stat(StatType::Speed) > bulbasaur.stat(StatType::Speed));
+
+        let tackle = dexes.movedex.get(&id("tackle")).value().clone();
+        let quick_attack = Move {
+            priority: 1,
+            ..tackle.clone()
+        };
+
+        let mut actions = [(&pidgey, &tackle), (&bulbasaur, &tackle)];
+        order_turn(&mut actions, &mut TestRng::new(1));
+        assert_eq!(actions[0].0.pokemon.id, PIDGEY);
+
+        let mut actions = [(&pidgey, &tackle), (&bulbasaur, &quick_attack)];
+        order_turn(&mut actions, &mut TestRng::new(1));
+        assert_eq!(actions[0].0.pokemon.id, BULBASAUR);
+    }
+
+    #[test]
+    fn paralysis_halves_speed() {
+        let dexes = Dexes::new();
+        let mut pidgey = dexes.owned(PIDGEY, 50);
+        let bulbasaur = dexes.owned(BULBASAUR, 50);
+        pidgey.ailment = Some(LiveAilment {
+            ailment: Ailment::Paralysis,
+            turns: None,
+        });
+        let tackle = dexes.movedex.get(&id("tackle")).value().clone();
+
+        let mut actions = [(&pidgey, &tackle), (&bulbasaur, &tackle)];
+        order_turn(&mut actions, &mut TestRng::new(1));
+        assert_eq!(actions[0].0.pokemon.id, BULBASAUR);
+    }
+
+    #[test]
+    fn accuracy_boundaries() {
+        let mut random = TestRng::new(1);
+        assert!((0..1000).all(|_| check_accuracy(Some(100), 0, 0, &mut random)));
+        // raised evasion cancels out raised accuracy
+        assert!((0..1000).all(|_| check_accuracy(Some(100), 2, 2, &mut random)));
+        assert!(!(0..1000).any(|_| check_accuracy(Some(0), 6, -6, &mut random)));
+
+        // a 100 accuracy move can still miss against raised evasion
+        let hits = (0..1000)
+            .filter(|_| check_accuracy(Some(100), 0, 6, &mut random))
+            .count();
+        assert!(hits > 0 && hits < 1000, "{}", hits);
+    }
+}
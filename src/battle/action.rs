@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use crate::item::ItemId;
+
+/// An action chosen by a player or AI during a battle turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum BattleAction {
+    /// Use the move at `index` of the active pokemon's move set on the pokemon at `target`.
+    UseMove { index: usize, target: usize },
+    /// Switch the active pokemon with the party member at this index.
+    Switch(usize),
+    /// Use an item on the party member at this index.
+    UseItem(ItemId, usize),
+    Run,
+}
+
+/// The random seed and actions needed to deterministically replay a battle.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub actions: Vec<BattleAction>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BattleAction, Replay};
+    use crate::testing::id;
+
+    #[test]
+    fn replay_round_trip() {
+        let replay = Replay {
+            seed: 1234,
+            actions: vec![
+                BattleAction::UseMove { index: 0, target: 1 },
+                BattleAction::Switch(2),
+                BattleAction::UseItem(id("potion"), 0),
+                BattleAction::Run,
+            ],
+        };
+        let json = serde_json::to_string(&replay).unwrap();
+        assert_eq!(serde_json::from_str::<Replay>(&json).unwrap(), replay);
+    }
+}
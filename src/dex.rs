@@ -12,10 +12,28 @@ impl<I: Identifiable> Dex<I> {
         Self(dex)
     }
 
+    /// Creates a dex holding a placeholder, whose id should be [Identifiable::UNKNOWN], so that [Dex::unknown] does not panic.
+    pub fn with_unknown(unknown: I) -> Self {
+        let mut dex = HashMap::with_capacity(1);
+        dex.insert(*unknown.id(), unknown);
+        Self(dex)
+    }
+
     pub fn inner_mut(&mut self) -> &mut HashMap<I::Id, I> {
         &mut self.0
     }
 
+    /// Inserts the entries of another dex into this one, replacing entries that share an id.
+    pub fn overlay(&mut self, other: Self) {
+        self.0.extend(other.0)
+    }
+
+    /// Overlays each dex on top of this one in order.
+    pub fn merge_many(mut self, others: impl IntoIterator<Item = Self>) -> Self {
+        others.into_iter().for_each(|other| self.overlay(other));
+        self
+    }
+
     pub fn try_get<'a>(&'a self, id: &I::Id) -> Option<IdRef<'a, I>> {
         self.0.get(id).map(IdRef::of)
     }
@@ -35,9 +53,21 @@ impl<I: Identifiable> Dex<I> {
             .unwrap_or_else(|| self.unknown())
     }
 
+    pub fn contains(&self, id: &I::Id) -> bool {
+        self.0.contains_key(id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = IdRef<'_, I>> + '_ {
+        self.0.values().map(IdRef::of)
+    }
+
     pub fn len(&self) -> usize {
         self.0.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 }
 
 /// Serialize Dex as a Vec
@@ -52,4 +82,56 @@ impl<'de, I: Identifiable + Deserialize<'de>> Deserialize<'de> for Dex<I> {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         Vec::<I>::deserialize(deserializer).map(|i| Dex(i.into_iter().map(|i| (*i.id(), i)).collect()))
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use crate::{
+        moves::Move,
+        pokemon::Pokemon,
+        testing::{dex, pokedex, stats, BULBASAUR, PIDGEY},
+        types::PokemonType,
+        Dex, Identifiable,
+    };
+
+    fn with_hp(pokemon: &Pokemon, hp: u8) -> Pokemon {
+        Pokemon {
+            base: stats(hp, 1, 1, 1, 1, 1),
+            ..pokemon.clone()
+        }
+    }
+
+    #[test]
+    fn overlay_replaces_matching_entries() {
+        let base = pokedex();
+        let mut pokedex = pokedex();
+        let len = pokedex.len();
+        pokedex.overlay(dex(vec![with_hp(&base.get(&PIDGEY), 100)]));
+
+        assert_eq!(pokedex.len(), len);
+        assert_eq!(pokedex.get(&PIDGEY).base.hp, 100);
+        assert_eq!(pokedex.get(&BULBASAUR).base, base.get(&BULBASAUR).base);
+    }
+
+    #[test]
+    fn merge_many_applies_overlays_in_order() {
+        let base = pokedex();
+        let pidgey = base.get(&PIDGEY);
+        let pokedex = pokedex().merge_many(vec![
+            dex(vec![with_hp(&pidgey, 100)]),
+            dex(vec![with_hp(&pidgey, 200)]),
+        ]);
+        assert_eq!(pokedex.get(&PIDGEY).base.hp, 200);
+    }
+
+    #[test]
+    fn with_unknown_does_not_panic() {
+        let pokedex = Dex::with_unknown(Pokemon::unknown());
+        assert_eq!(pokedex.len(), 1);
+        assert_eq!(pokedex.unknown().id, Pokemon::UNKNOWN);
+        assert_eq!(pokedex.get(&PIDGEY).name, "Unknown");
+
+        let movedex = Dex::with_unknown(Move::unknown());
+        assert_eq!(movedex.unknown().id, Move::UNKNOWN);
+        assert_eq!(movedex.unknown().pokemon_type, PokemonType::Normal);
+    }
+}
@@ -30,6 +30,12 @@ impl<'a, I: Identifiable> Clone for IdRef<'a, I> {
 
 impl<'a, I: Identifiable> Copy for IdRef<'a, I> {}
 
+impl<'a, I: Identifiable> PartialEq for IdRef<'a, I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
 impl<'a, I: Identifiable> Debug for IdRef<'a, I> {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         Display::fmt(self.id(), f)
@@ -4,7 +4,9 @@ use tinystr::TinyStr16;
 
 use crate::{
     Dex, Identifiable, IdRef,
+    ailment::Ailment,
     item::usage::ItemUsage,
+    moves::Power,
 };
 
 pub mod bag;
@@ -36,6 +38,14 @@ pub struct Item {
 
     #[serde(default)]
     pub usage: ItemUsage,
+
+    /// The power of Fling when this item is thrown. If it is [None], the item cannot be flung.
+    #[serde(default)]
+    pub fling_power: Option<Power>,
+
+    /// The ailment Fling inflicts when this item is thrown.
+    #[serde(default)]
+    pub fling_effect: Option<Ailment>,
 }
 
 impl Identifiable for Item {
@@ -56,15 +66,10 @@ impl Item {
     
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
 pub enum ItemCategory {
+    #[default]
     Items,
     KeyItems,
     Pokeballs,
-}
-
-impl Default for ItemCategory {
-    fn default() -> Self {
-        Self::Items
-    }
 }
\ No newline at end of file
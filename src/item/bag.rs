@@ -1,4 +1,4 @@
-use crate::item::{ItemId, ItemIdStack, ItemRefStack, Itemdex};
+use crate::item::{ItemId, ItemIdStack, ItemRefStack, Itemdex, StackSize};
 
 pub struct Bag<'d> {
     pub itemdex: &'d Itemdex,
@@ -15,6 +15,23 @@ impl<'d> Bag<'d> {
         self.items.iter().position(|stack| &stack.item.id == id)
     }
 
+    pub fn count(&self, id: &ItemId) -> StackSize {
+        self.position(id)
+            .map(|index| self.items[index].count)
+            .unwrap_or_default()
+    }
+
+    /// Removes an amount of an item from the bag. Nothing is removed if there is not enough of the item.
+    pub fn take_item(&mut self, id: &ItemId, count: StackSize) -> bool {
+        match self.position(id) {
+            Some(index) if self.items[index].count >= count => {
+                self.items[index].count -= count;
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub fn use_item(&mut self, id: &ItemId) -> bool {
         self.position(id)
             .map(|index| self.items[index].decrement())
@@ -26,6 +26,35 @@ impl<I> ItemStack<I> {
     }
 }
 
+impl<I: Clone> ItemStack<I> {
+    /// Splits off part of the stack. Returns [None] if the stack does not have that many items.
+    pub fn split(&mut self, count: StackSize) -> Option<Self> {
+        match count > 0 && count <= self.count {
+            true => {
+                self.count -= count;
+                Some(Self::new(self.item.clone(), count))
+            }
+            false => None,
+        }
+    }
+}
+
+impl<I: PartialEq> ItemStack<I> {
+    /// Merges a stack of the same item into this one, holding up to `stack_size` items.
+    /// Returns the items that do not fit, or the other stack unchanged if it holds a different item.
+    pub fn merge(&mut self, other: Self, stack_size: StackSize) -> Option<Self> {
+        if self.item != other.item {
+            return Some(other);
+        }
+        let count = self.count.saturating_add(other.count);
+        self.count = count.min(stack_size);
+        match count > stack_size {
+            true => Some(Self::new(other.item, count - stack_size)),
+            false => None,
+        }
+    }
+}
+
 impl ItemIdStack {
 
     pub fn init<'d>(self, itemdex: &'d Itemdex) -> Option<ItemRefStack<'d>> {
@@ -61,3 +90,57 @@ impl<'a> ItemRefStack<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ItemStack;
+    use crate::testing::{id, itemdex};
+
+    #[test]
+    fn merge_partial_stacks() {
+        let itemdex = itemdex();
+        let potion = itemdex.try_get(&id("potion")).unwrap();
+
+        let size = potion.stack_size;
+
+        let mut stack = ItemStack::new(potion, 500);
+        assert!(stack.merge(ItemStack::new(potion, 200), size).is_none());
+        assert_eq!(stack.count, 700);
+
+        let overflow = stack.merge(ItemStack::new(potion, 400), size).unwrap();
+        assert_eq!(stack.count, 999);
+        assert_eq!(overflow.count, 101);
+
+        let ether = itemdex.try_get(&id("ether")).unwrap();
+        let other = stack.merge(ItemStack::new(ether, 5), size).unwrap();
+        assert_eq!(other.item.id, id("ether"));
+        assert_eq!(other.count, 5);
+        assert_eq!(stack.count, 999);
+    }
+
+    #[test]
+    fn merge_id_stacks() {
+        let mut stack = ItemStack::new(id("potion"), 8);
+        assert!(stack.merge(ItemStack::new(id("potion"), 1), 10).is_none());
+        assert_eq!(stack.count, 9);
+
+        let overflow = stack.merge(ItemStack::new(id("potion"), 3), 10).unwrap();
+        assert_eq!((stack.count, overflow.count), (10, 2));
+
+        let other = stack.merge(ItemStack::new(id("ether"), 3), 10).unwrap();
+        assert_eq!(other.item, id("ether"));
+        assert_eq!(stack.count, 10);
+    }
+
+    #[test]
+    fn split_in_half() {
+        let mut stack = ItemStack::new(id("potion"), 10);
+        let half = stack.split(5).unwrap();
+        assert_eq!((stack.count, half.count), (5, 5));
+        assert_eq!(half.item, id("potion"));
+
+        assert!(stack.split(6).is_none());
+        assert!(stack.split(0).is_none());
+        assert_eq!(stack.count, 5);
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{pokemon::Health, ailment::Ailment};
+use crate::{pokemon::Health, ailment::Ailment, moves::PP};
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct ItemUsage {
@@ -16,12 +16,13 @@ const fn t() -> bool {
     true
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub enum ItemUsageKind {
     Actions(Vec<ItemAction>),
     Script,
     Pokeball,
+    #[default]
     None,
 }
 
@@ -36,10 +37,16 @@ pub enum ItemCondition {
 pub enum ItemAction {
     CurePokemon(Option<Ailment>),
     HealPokemon(Health),
+    /// Restores PP of a pokemon's moves. If it is [None], PP is fully restored.
+    RestorePP(Option<PP>),
 }
 
-impl Default for ItemUsageKind {
-    fn default() -> Self {
-        Self::None
-    }
+/// What happened when an item was used on a pokemon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemOutcome {
+    Cured(Ailment),
+    /// The amount of HP restored.
+    Healed(Health),
+    /// The index of the move and the amount of PP restored.
+    RestoredPP(usize, PP),
 }
\ No newline at end of file
@@ -1,16 +1,72 @@
 pub mod ailment;
+pub mod battle;
 pub mod item;
 pub mod moves;
 pub mod pokemon;
+pub mod rules;
+pub mod trainer;
 pub mod types;
 
 mod dex;
 pub use dex::*;
 
+mod checksum;
+
 pub mod id;
 pub use id::{IdRef, Identifiable};
 
+#[cfg(test)]
+mod testing;
+
+use crate::{
+    moves::{MoveId, Movedex},
+    pokemon::{Pokedex, PokemonId},
+};
+
+/// Returns every move referenced by a pokemon's learnset that is missing from the movedex.
+pub fn validate_learnsets(pokedex: &Pokedex, movedex: &Movedex) -> Vec<(PokemonId, MoveId)> {
+    pokedex
+        .iter()
+        .flat_map(|pokemon| {
+            pokemon
+                .value()
+                .moves
+                .iter()
+                .filter(|learnable| !movedex.contains(&learnable.1))
+                .map(move |learnable| (pokemon.value().id, learnable.1))
+        })
+        .collect()
+}
+
 fn name<T: ?Sized>() -> &'static str {
     let name = core::any::type_name::<T>();
     name.split("::").last().unwrap_or(name)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use crate::{
+        pokemon::LearnableMove,
+        testing::{id, movedex, pokedex, PIDGEY},
+        validate_learnsets,
+    };
+
+    #[test]
+    fn learnsets_reference_known_moves() {
+        assert!(validate_learnsets(&pokedex(), &movedex()).is_empty());
+    }
+
+    #[test]
+    fn missing_learnset_moves_are_found() {
+        let mut pokedex = pokedex();
+        pokedex
+            .inner_mut()
+            .get_mut(&PIDGEY)
+            .unwrap()
+            .moves
+            .push(LearnableMove(9, id("quick_attack")));
+        assert_eq!(
+            validate_learnsets(&pokedex, &movedex()),
+            vec![(PIDGEY, id("quick_attack"))]
+        );
+    }
+}
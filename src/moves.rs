@@ -1,9 +1,17 @@
-use core::fmt::{Display, Formatter, Result as FmtResult};
+use core::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    ops::RangeInclusive,
+};
+use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
 use tinystr::TinyStr16;
 
 use crate::{
-    id::UNKNOWN_ID, pokemon::stat::StatType, types::PokemonType, Dex, IdRef, Identifiable,
+    ailment::{AilmentEffect, LiveAilment},
+    id::UNKNOWN_ID,
+    pokemon::{stat::StatType, Pokemon},
+    types::PokemonType,
+    Dex, IdRef, Identifiable,
 };
 
 mod owned;
@@ -15,6 +23,9 @@ pub use set::*;
 mod target;
 pub use target::*;
 
+mod flags;
+pub use flags::*;
+
 /// A Move's identifier
 pub type MoveId = TinyStr16;
 pub type Power = u8;
@@ -28,7 +39,7 @@ pub type MoveRef<'a> = IdRef<'a, Move>;
 pub type Movedex = Dex<Move>;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
-#[serde(deny_unknown_fields)]
+#[serde(from = "MoveData")]
 pub struct Move {
     /// A Move's identifier
     pub id: MoveId,
@@ -54,13 +65,22 @@ pub struct Move {
     #[serde(default)]
     pub priority: Priority,
 
+    /// How many times the move hits, such as 2 - 5 for Fury Attack.
+    /// If it is [None], the move hits once.
+    #[serde(default)]
+    pub hits: Option<RangeInclusive<u8>>,
+
+    /// An ailment the move can inflict on the target and the percent chance (0 - 100) of it doing so.
+    #[serde(default)]
+    pub ailment: Option<(AilmentEffect, u8)>,
+
     /// The target of the move.
     #[serde(default)]
     pub target: target::MoveTarget,
 
-    /// If the move makes contact with the target.
+    /// Properties of the move, such as if it makes contact with the target.
     #[serde(default)]
-    pub contact: bool,
+    pub flags: MoveFlags,
 
     /// Increments the chance of whether a move should critical hit or not.
     #[serde(default)]
@@ -69,9 +89,161 @@ pub struct Move {
     /// World moves are also known as field moves. This boolean tells if this move is a world move.
     #[serde(default)]
     pub world: bool,
+
+    /// A description of the move.
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// A short description of what the move does.
+    #[serde(default)]
+    pub short_effect: Option<String>,
+
+    /// Game specific data (such as animations) that is kept but not used by this crate.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra: HashMap<String, String>,
+}
+
+/// The serialized form of a [Move], which also accepts the `contact` field moves had before [MoveFlags].
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MoveData {
+    id: MoveId,
+    name: String,
+    category: MoveCategory,
+    #[serde(rename = "type")]
+    pokemon_type: PokemonType,
+    accuracy: Option<Accuracy>,
+    power: Option<Power>,
+    pp: PP,
+    #[serde(default)]
+    priority: Priority,
+    #[serde(default)]
+    hits: Option<RangeInclusive<u8>>,
+    #[serde(default)]
+    ailment: Option<(AilmentEffect, u8)>,
+    #[serde(default)]
+    target: target::MoveTarget,
+    #[serde(default)]
+    flags: MoveFlags,
+    /// Replaced by [MoveFlag::Contact].
+    #[serde(default)]
+    contact: bool,
+    #[serde(default)]
+    crit_rate: CriticalRate,
+    #[serde(default)]
+    world: bool,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    short_effect: Option<String>,
+    #[serde(default)]
+    extra: HashMap<String, String>,
+}
+
+impl From<MoveData> for Move {
+    fn from(data: MoveData) -> Self {
+        let mut flags = data.flags;
+        if data.contact {
+            flags.insert(MoveFlags::CONTACT);
+        }
+        Self {
+            id: data.id,
+            name: data.name,
+            category: data.category,
+            pokemon_type: data.pokemon_type,
+            accuracy: data.accuracy,
+            power: data.power,
+            pp: data.pp,
+            priority: data.priority,
+            hits: data.hits,
+            ailment: data.ailment,
+            target: data.target,
+            flags,
+            crit_rate: data.crit_rate,
+            world: data.world,
+            description: data.description,
+            short_effect: data.short_effect,
+            extra: data.extra,
+        }
+    }
 }
 
 impl Move {
+    /// A placeholder for [Move::UNKNOWN].
+    pub fn unknown() -> Self {
+        Self {
+            id: Self::UNKNOWN,
+            name: "Unknown".to_owned(),
+            category: MoveCategory::Status,
+            pokemon_type: PokemonType::Normal,
+            accuracy: None,
+            power: None,
+            pp: 0,
+            priority: Default::default(),
+            hits: Default::default(),
+            ailment: Default::default(),
+            target: Default::default(),
+            flags: Default::default(),
+            crit_rate: Default::default(),
+            world: Default::default(),
+            description: Default::default(),
+            short_effect: Default::default(),
+            extra: Default::default(),
+        }
+    }
+
+    pub fn description(&self) -> &str {
+        self.description.as_deref().unwrap_or_default()
+    }
+
+    pub fn short_effect(&self) -> &str {
+        self.short_effect.as_deref().unwrap_or_default()
+    }
+
+    pub fn has_flag(&self, flags: impl Into<MoveFlags>) -> bool {
+        self.flags.contains(flags.into())
+    }
+
+    /// Checks if a status move can affect the target's types.
+    /// Powder moves do not affect Grass types, and Electric status moves (such as Thunder Wave) do not affect Ground types.
+    pub fn affects_target(&self, target: &Pokemon) -> bool {
+        !(self.has_flag(MoveFlag::Powder) && target.has_type(PokemonType::Grass)
+            || self.category == MoveCategory::Status
+                && self.pokemon_type == PokemonType::Electric
+                && target.has_type(PokemonType::Ground))
+    }
+
+    /// The critical hit stage of the move, from 0 to 3.
+    pub fn crit_stage(&self) -> u8 {
+        self.crit_rate.min(3)
+    }
+
+    /// Rolls how many times the move hits.
+    /// Moves that hit 2 - 5 times hit 2 or 3 times 35% of the time each, and 4 or 5 times 15% of the time each.
+    /// Other ranges are rolled uniformly.
+    pub fn roll_hits(&self, random: &mut impl rand::Rng) -> u8 {
+        match &self.hits {
+            None => 1,
+            Some(hits) if (*hits.start(), *hits.end()) == (2, 5) => match random.gen_range(0..20) {
+                0..=6 => 2,
+                7..=13 => 3,
+                14..=16 => 4,
+                _ => 5,
+            },
+            Some(hits) if hits.is_empty() => *hits.start(),
+            Some(hits) => random.gen_range(hits.clone()),
+        }
+    }
+
+    /// Rolls whether the move inflicts its ailment, returning the ailment if it does.
+    pub fn roll_ailment(&self, random: &mut impl rand::Rng) -> Option<LiveAilment> {
+        let (effect, chance) = self.ailment.as_ref()?;
+        match random.gen_range(0..100) < *chance {
+            true => Some(effect.turns.init(effect.ailment, random)),
+            false => None,
+        }
+    }
+
     pub fn try_hit(&self, random: &mut impl rand::Rng) -> bool {
         self.accuracy
             .map(|accuracy| random.gen_range(0..100) < accuracy)
@@ -129,3 +301,209 @@ impl MoveCategory {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ailment::{Ailment, AilmentLength},
+        testing::{move_with, pokedex, TestRng, BULBASAUR, GEODUDE, PIDGEY},
+    };
+
+    const TACKLE: &str = r#"{
+        "id": "tackle",
+        "name": "Tackle",
+        "category": "Physical",
+        "type": "Normal",
+        "accuracy": 100,
+        "power": 40,
+        "pp": 35
+    }"#;
+
+    fn with_field(field: &str) -> String {
+        TACKLE.replacen("\"pp\": 35", &format!("\"pp\": 35, {}", field), 1)
+    }
+
+    #[test]
+    fn flags_round_trip() {
+        let m: Move = serde_json::from_str(&with_field(r#""flags": ["Contact", "Punch"]"#)).unwrap();
+        assert!(m.has_flag(MoveFlag::Contact));
+        assert!(m.has_flag(MoveFlags::CONTACT | MoveFlags::PUNCH));
+        assert!(!m.has_flag(MoveFlag::Sound));
+
+        let json = serde_json::to_string(&m).unwrap();
+        let m: Move = serde_json::from_str(&json).unwrap();
+        assert_eq!(m.flags, MoveFlags::CONTACT | MoveFlags::PUNCH);
+    }
+
+    #[test]
+    fn no_flags() {
+        let m: Move = serde_json::from_str(TACKLE).unwrap();
+        assert_eq!(m.flags, MoveFlags::NONE);
+        assert!(!m.has_flag(MoveFlag::Contact));
+    }
+
+    #[test]
+    fn legacy_contact() {
+        let m: Move = serde_json::from_str(&with_field(r#""contact": true"#)).unwrap();
+        assert!(m.has_flag(MoveFlag::Contact));
+
+        let m: Move = serde_json::from_str(&with_field(r#""contact": false"#)).unwrap();
+        assert!(!m.has_flag(MoveFlag::Contact));
+
+        let m: Move =
+            serde_json::from_str(&with_field(r#""contact": true, "flags": ["Sound"]"#)).unwrap();
+        assert_eq!(m.flags, MoveFlags::CONTACT | MoveFlags::SOUND);
+
+        // contact is read but not written back
+        let json = serde_json::to_string(&m).unwrap();
+        assert!(!json.contains("\"contact\""));
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        assert!(serde_json::from_str::<Move>(&with_field(r#""bogus": 1"#)).is_err());
+    }
+
+    #[test]
+    fn description_round_trip() {
+        let m: Move = serde_json::from_str(&with_field(
+            r#""description": "A physical attack.", "short_effect": "Inflicts damage.""#,
+        ))
+        .unwrap();
+        assert_eq!(m.description(), "A physical attack.");
+        assert_eq!(m.short_effect(), "Inflicts damage.");
+
+        let json = serde_json::to_string(&m).unwrap();
+        let m: Move = serde_json::from_str(&json).unwrap();
+        assert_eq!(m.description(), "A physical attack.");
+        assert_eq!(m.short_effect(), "Inflicts damage.");
+    }
+
+    #[test]
+    fn no_description() {
+        let m: Move = serde_json::from_str(TACKLE).unwrap();
+        assert_eq!(m.description(), "");
+        assert_eq!(m.short_effect(), "");
+    }
+
+    #[test]
+    fn extra_data_round_trip() {
+        let m: Move =
+            serde_json::from_str(&with_field(r#""extra": { "animation": "tackle_hit" }"#)).unwrap();
+        assert_eq!(m.extra.get("animation").map(String::as_str), Some("tackle_hit"));
+
+        let json = serde_json::to_string(&m).unwrap();
+        let m: Move = serde_json::from_str(&json).unwrap();
+        assert_eq!(m.extra.get("animation").map(String::as_str), Some("tackle_hit"));
+
+        // empty extra data is not written
+        let json = serde_json::to_string(&serde_json::from_str::<Move>(TACKLE).unwrap()).unwrap();
+        assert!(!json.contains("\"extra\""));
+    }
+
+    #[test]
+    fn status_move_immunities() {
+        let pokedex = pokedex();
+        let sleep_powder = Move {
+            flags: MoveFlags::POWDER,
+            ..move_with("sleep_powder", PokemonType::Grass, MoveCategory::Status, None, Some(75), 15)
+        };
+        let thunder_wave = move_with("thunder_wave", PokemonType::Electric, MoveCategory::Status, None, Some(90), 20);
+        let thunder_shock = move_with("thunder_shock", PokemonType::Electric, MoveCategory::Special, Some(40), Some(100), 30);
+
+        assert!(!sleep_powder.affects_target(&pokedex.get(&BULBASAUR)));
+        assert!(sleep_powder.affects_target(&pokedex.get(&PIDGEY)));
+
+        assert!(!thunder_wave.affects_target(&pokedex.get(&GEODUDE)));
+        assert!(thunder_wave.affects_target(&pokedex.get(&PIDGEY)));
+        // damaging moves are handled by type effectiveness instead
+        assert!(thunder_shock.affects_target(&pokedex.get(&GEODUDE)));
+    }
+
+    #[test]
+    fn fixed_hits() {
+        let mut random = TestRng::new(1);
+        let tackle: Move = serde_json::from_str(TACKLE).unwrap();
+        assert_eq!(tackle.roll_hits(&mut random), 1);
+
+        let double_kick = Move {
+            hits: Some(2..=2),
+            ..tackle
+        };
+        assert!((0..100).all(|_| double_kick.roll_hits(&mut random) == 2));
+    }
+
+    #[test]
+    fn weighted_hits() {
+        let mut random = TestRng::new(1);
+        let fury_attack: Move =
+            serde_json::from_str(&with_field(r#""hits": { "start": 2, "end": 5 }"#)).unwrap();
+        let mut counts = [0; 6];
+        for _ in 0..2000 {
+            counts[fury_attack.roll_hits(&mut random) as usize] += 1;
+        }
+        assert_eq!(counts[..2], [0, 0]);
+        // 35%, 35%, 15% and 15% of 2000 rolls
+        assert!((600..800).contains(&counts[2]), "{:?}", counts);
+        assert!((600..800).contains(&counts[3]), "{:?}", counts);
+        assert!((200..400).contains(&counts[4]), "{:?}", counts);
+        assert!((200..400).contains(&counts[5]), "{:?}", counts);
+
+        let uniform = Move {
+            hits: Some(1..=3),
+            ..fury_attack
+        };
+        assert!((0..100).all(|_| (1..=3).contains(&uniform.roll_hits(&mut random))));
+    }
+
+    #[test]
+    fn ailment_chance_extremes() {
+        let mut random = TestRng::new(1);
+        let burn = |chance| Move {
+            ailment: Some((
+                AilmentEffect {
+                    ailment: Ailment::Burn,
+                    turns: AilmentLength::Permanent,
+                },
+                chance,
+            )),
+            ..serde_json::from_str(TACKLE).unwrap()
+        };
+
+        let never = burn(0);
+        assert!((0..1000).all(|_| never.roll_ailment(&mut random).is_none()));
+
+        let always = burn(100);
+        assert!((0..1000).all(|_| {
+            always.roll_ailment(&mut random)
+                == Some(LiveAilment {
+                    ailment: Ailment::Burn,
+                    turns: None,
+                })
+        }));
+
+        let tackle: Move = serde_json::from_str(TACKLE).unwrap();
+        assert!(tackle.roll_ailment(&mut random).is_none());
+    }
+
+    #[test]
+    fn temporary_ailments_roll_their_length() {
+        let mut random = TestRng::new(1);
+        let sleep = Move {
+            ailment: Some((
+                AilmentEffect {
+                    ailment: Ailment::Sleep,
+                    turns: AilmentLength::Temporary(1, 3),
+                },
+                100,
+            )),
+            ..serde_json::from_str(TACKLE).unwrap()
+        };
+        for _ in 0..100 {
+            let ailment = sleep.roll_ailment(&mut random).unwrap();
+            assert_eq!(ailment.ailment, Ailment::Sleep);
+            assert!((1..=3).contains(&ailment.turns.unwrap()));
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use core::ops::{BitOr, BitOrAssign};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A property of a move that other mechanics (abilities, items) can check for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum MoveFlag {
+    /// The move makes contact with the target.
+    Contact,
+    /// The move is sound-based.
+    Sound,
+    /// The move is a punching move.
+    Punch,
+    /// The move is a biting move.
+    Bite,
+    /// The move is a powder or spore move.
+    Powder,
+    /// The move is a pulse or aura move.
+    Pulse,
+    /// The move is a ballistic (ball or bomb) move.
+    Ballistic,
+}
+
+impl MoveFlag {
+    pub const ALL: [MoveFlag; 7] = [
+        MoveFlag::Contact,
+        MoveFlag::Sound,
+        MoveFlag::Punch,
+        MoveFlag::Bite,
+        MoveFlag::Powder,
+        MoveFlag::Pulse,
+        MoveFlag::Ballistic,
+    ];
+
+    const fn bit(self) -> u16 {
+        1 << self as u16
+    }
+}
+
+/// A set of [MoveFlag]s stored as a bitset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MoveFlags(u16);
+
+impl MoveFlags {
+    pub const NONE: Self = Self(0);
+    pub const CONTACT: Self = Self(MoveFlag::Contact.bit());
+    pub const SOUND: Self = Self(MoveFlag::Sound.bit());
+    pub const PUNCH: Self = Self(MoveFlag::Punch.bit());
+    pub const BITE: Self = Self(MoveFlag::Bite.bit());
+    pub const POWDER: Self = Self(MoveFlag::Powder.bit());
+    pub const PULSE: Self = Self(MoveFlag::Pulse.bit());
+    pub const BALLISTIC: Self = Self(MoveFlag::Ballistic.bit());
+
+    pub const fn contains(&self, flags: Self) -> bool {
+        self.0 & flags.0 == flags.0
+    }
+
+    pub fn insert(&mut self, flags: Self) {
+        self.0 |= flags.0;
+    }
+
+    pub fn remove(&mut self, flags: Self) {
+        self.0 &= !flags.0;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = MoveFlag> + '_ {
+        MoveFlag::ALL
+            .iter()
+            .copied()
+            .filter(move |flag| self.contains((*flag).into()))
+    }
+}
+
+impl From<MoveFlag> for MoveFlags {
+    fn from(flag: MoveFlag) -> Self {
+        Self(flag.bit())
+    }
+}
+
+impl BitOr for MoveFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for MoveFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.insert(rhs)
+    }
+}
+
+/// Serialize MoveFlags as a list of flags
+impl Serialize for MoveFlags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+/// Deserialize MoveFlags from a list of flags
+impl<'de> Deserialize<'de> for MoveFlags {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<MoveFlag>::deserialize(deserializer)
+            .map(|flags| flags.into_iter().fold(Self::NONE, |set, flag| set | flag.into()))
+    }
+}
@@ -10,23 +10,51 @@ pub struct OwnedMove<M, P> {
     #[serde(rename = "move")]
     pub m: M,
     pub pp: P,
+    /// The number of PP Ups used on the move (0 - 3).
+    #[serde(default)]
+    pub pp_ups: u8,
     // pub decrement: Option<PP>,
 }
 
+impl<M, P> OwnedMove<M, P> {
+    pub const MAX_PP_UPS: u8 = 3;
+}
+
 impl OwnedIdMove {
 
-    pub fn init<'d>(self, movedex: &'d Movedex) -> Option<OwnedRefMove> {
+    pub fn init(self, movedex: &Movedex) -> Option<OwnedRefMove<'_>> {
         let m = movedex.try_get(&self.m)?;
-        Some(OwnedRefMove {
-            pp: self.pp.unwrap_or(m.pp),
-            m
-        })
+        let mut owned = OwnedRefMove {
+            pp: 0,
+            pp_ups: self.pp_ups.min(OwnedRefMove::MAX_PP_UPS),
+            m,
+        };
+        owned.pp = self.pp.unwrap_or(owned.max_pp()).min(owned.max_pp());
+        Some(owned)
     }
 }
 
 impl<'d> OwnedRefMove<'d> {
     pub fn new(m: MoveRef<'d>) -> Self {
-        Self { pp: m.pp, m }
+        Self { pp: m.pp, pp_ups: 0, m }
+    }
+
+    /// The maximum PP of the move. Each PP Up raises it by a fifth of the move's base PP.
+    pub fn max_pp(&self) -> PP {
+        let ups = self.pp_ups.min(Self::MAX_PP_UPS) as u16;
+        (self.m.pp as u16 + self.m.pp as u16 * ups / 5).min(PP::MAX as u16) as PP
+    }
+
+    /// Uses a PP Up on the move, raising its maximum and current PP.
+    /// Returns false if the move already has the maximum number of PP Ups.
+    pub fn add_pp_up(&mut self) -> bool {
+        if self.pp_ups >= Self::MAX_PP_UPS {
+            return false;
+        }
+        let previous = self.max_pp();
+        self.pp_ups += 1;
+        self.pp = self.pp.saturating_add(self.max_pp() - previous);
+        true
     }
 
     pub fn try_use(&self) -> Option<&MoveRef<'d>> {
@@ -36,6 +64,17 @@ impl<'d> OwnedRefMove<'d> {
         }
     }
 
+    /// Spends one PP to use the move. Returns false if the move has no PP left.
+    pub fn use_move(&mut self) -> bool {
+        match self.empty() {
+            false => {
+                self.pp -= 1;
+                true
+            }
+            true => false,
+        }
+    }
+
     pub fn decrement(&mut self) {
         self.pp = self.pp.saturating_sub(1);
     }
@@ -45,13 +84,23 @@ impl<'d> OwnedRefMove<'d> {
     }
 
     pub fn restore(&mut self, amount: Option<PP>) {
-        self.pp = amount.unwrap_or(self.m.pp).min(self.m.pp)
+        self.pp = amount.unwrap_or_else(|| self.max_pp()).min(self.max_pp())
+    }
+
+    /// Adds PP to the move without going over its maximum. If no amount is given, PP is fully restored.
+    /// Returns the amount of PP restored.
+    pub fn add_pp(&mut self, amount: Option<PP>) -> PP {
+        let max = self.max_pp();
+        let restored = amount.unwrap_or(max).min(max.saturating_sub(self.pp));
+        self.pp += restored;
+        restored
     }
 
     pub fn uninit(self) -> OwnedIdMove {
         OwnedIdMove {
             m: self.m.id,
             pp: Some(self.pp),
+            pp_ups: self.pp_ups,
         }
     }
 
@@ -59,6 +108,80 @@ impl<'d> OwnedRefMove<'d> {
 
 impl From<MoveId> for OwnedIdMove {
     fn from(id: MoveId) -> Self {
-        Self { m: id, pp: None }
+        Self { m: id, pp: None, pp_ups: 0 }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::{OwnedIdMove, OwnedRefMove};
+    use crate::{
+        moves::{MoveCategory, MoveRefSet, MoveSet, Movedex},
+        testing::{dex, id, move_with},
+        types::PokemonType,
+    };
+
+    fn movedex() -> Movedex {
+        dex(vec![
+            move_with("ten", PokemonType::Normal, MoveCategory::Physical, Some(40), Some(100), 10),
+            move_with("one", PokemonType::Normal, MoveCategory::Physical, Some(40), Some(100), 1),
+        ])
+    }
+
+    #[test]
+    fn pp_ups_raise_max_pp() {
+        let movedex = movedex();
+        let mut m = OwnedRefMove::new(movedex.get(&id("ten")));
+        assert_eq!(m.max_pp(), 10);
+        for max in [12, 14, 16] {
+            assert!(m.add_pp_up());
+            assert_eq!(m.max_pp(), max);
+            assert_eq!(m.pp, max);
+        }
+        assert!(!m.add_pp_up());
+        assert_eq!(m.max_pp(), 16);
+
+        m.pp = 0;
+        m.restore(None);
+        assert_eq!(m.pp, 16);
+        m.pp = 0;
+        assert_eq!(m.add_pp(None), 16);
+    }
+
+    #[test]
+    fn pp_ups_are_saved() {
+        let movedex = movedex();
+        let saved = OwnedIdMove {
+            m: id("ten"),
+            pp: None,
+            pp_ups: 5,
+        };
+        let m = saved.init(&movedex).unwrap();
+        // illegal PP Ups are capped when loaded
+        assert_eq!(m.pp_ups, 3);
+        assert_eq!(m.pp, 16);
+        assert_eq!(m.uninit().pp_ups, 3);
+    }
+
+    #[test]
+    fn use_move_spends_pp() {
+        let movedex = movedex();
+        let mut m = OwnedRefMove::new(movedex.get(&id("one")));
+        assert!(m.use_move());
+        assert_eq!(m.pp, 0);
+        assert!(!m.use_move());
+        assert_eq!(m.pp, 0);
+        assert!(m.try_use().is_none());
+    }
+
+    #[test]
+    fn use_move_by_index() {
+        let movedex = movedex();
+        let mut set = MoveSet::new();
+        set.push(OwnedRefMove::new(movedex.get(&id("one"))));
+        let mut set = MoveRefSet::new(&movedex, set);
+        assert!(set.use_move(0));
+        assert!(!set.use_move(0));
+        // there is no move at this index
+        assert!(!set.use_move(1));
+    }
+}
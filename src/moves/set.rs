@@ -1,6 +1,6 @@
 use core::ops::{Deref, DerefMut};
 
-use super::{OwnedRefMove, Movedex, OwnedMove, MoveId};
+use super::{OwnedRefMove, Movedex, OwnedMove, MoveId, MoveRef};
 
 pub const MOVESET_LENGTH: usize = 4;
 
@@ -10,6 +10,15 @@ pub type OwnedMoveSet<M, P> = MoveSet<OwnedMove<M, P>>;
 
 type RefSet<'d> = MoveSet<OwnedRefMove<'d>>;
 
+/// The result of trying to learn a move.
+#[derive(Debug, Clone, Copy)]
+pub enum LearnResult<'d> {
+    /// The move was added to the set.
+    Learned,
+    /// The set is full, so a move has to be replaced with [MoveRefSet::replace] to learn the pending one.
+    MustReplace(MoveRef<'d>),
+}
+
 pub struct MoveRefSet<'d> {
     pub movedex: &'d Movedex,
     pub set: RefSet<'d>,
@@ -27,6 +36,52 @@ impl<'d> MoveRefSet<'d> {
         self.set.iter().filter(|o| o.m.world).map(|o| &o.m.id)
     }
 
+    /// Spends one PP of the move at the index.
+    /// Returns false if there is no move at the index or it has no PP left.
+    pub fn use_move(&mut self, index: usize) -> bool {
+        self.set.get_mut(index).map(OwnedRefMove::use_move).unwrap_or_default()
+    }
+
+    /// Learns a move if there is space for it. If the set is full, the move is given back to be replaced.
+    pub fn learn(&mut self, m: MoveRef<'d>) -> LearnResult<'d> {
+        match self.set.try_push(OwnedRefMove::new(m)) {
+            Ok(()) => LearnResult::Learned,
+            Err(..) => LearnResult::MustReplace(m),
+        }
+    }
+
+    /// Replaces the move at the index with a pending move, returning the forgotten move.
+    /// Returns [None] and does nothing if there is no move at the index.
+    pub fn replace(&mut self, index: usize, pending: MoveRef<'d>) -> Option<OwnedRefMove<'d>> {
+        self.set
+            .get_mut(index)
+            .map(|m| core::mem::replace(m, OwnedRefMove::new(pending)))
+    }
+
+    /// Swaps the moves at two indexes. Does nothing if either index has no move.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        if a < self.set.len() && b < self.set.len() {
+            self.set.swap(a, b);
+        }
+    }
+
+    /// Moves the move at `from` to `to`, shifting the moves in between.
+    /// Does nothing if either index has no move.
+    pub fn move_to(&mut self, from: usize, to: usize) {
+        if from < self.set.len() && to < self.set.len() {
+            match from < to {
+                true => self.set[from..=to].rotate_left(1),
+                false => self.set[to..=from].rotate_right(1),
+            }
+        }
+    }
+
+    /// Forgets the move at the index, moving the moves after it down.
+    /// Returns the forgotten move, or [None] if there is no move at the index.
+    pub fn forget(&mut self, index: usize) -> Option<OwnedRefMove<'d>> {
+        self.set.pop_at(index)
+    }
+
 }
 
 impl<'d> Deref for MoveRefSet<'d> {
@@ -56,4 +111,80 @@ impl<'d> core::fmt::Debug for MoveRefSet<'d> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         core::fmt::Debug::fmt(&self.set, f)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::{LearnResult, MoveRefSet, OwnedRefMove};
+    use crate::{
+        moves::{MoveId, Movedex},
+        testing::{id, movedex},
+    };
+
+    fn set<'d>(movedex: &'d Movedex, moves: &[&str]) -> MoveRefSet<'d> {
+        MoveRefSet::new(
+            movedex,
+            moves
+                .iter()
+                .map(|m| OwnedRefMove::new(movedex.get(&id(m))))
+                .collect(),
+        )
+    }
+
+    fn ids(set: &MoveRefSet) -> Vec<MoveId> {
+        set.iter().map(|m| m.m.id).collect()
+    }
+
+    #[test]
+    fn forget_compacts_the_set() {
+        let movedex = movedex();
+        let mut set = set(&movedex, &["tackle", "growl", "ember"]);
+        assert_eq!(set.forget(1).map(|m| m.m.id), Some(id("growl")));
+        assert_eq!(ids(&set), vec![id("tackle"), id("ember")]);
+        assert!(set.forget(2).is_none());
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn learn_into_empty_and_partial_sets() {
+        let movedex = movedex();
+        let mut set = set(&movedex, &[]);
+        assert!(matches!(set.learn(movedex.get(&id("tackle"))), LearnResult::Learned));
+        assert!(matches!(set.learn(movedex.get(&id("growl"))), LearnResult::Learned));
+        assert_eq!(ids(&set), vec![id("tackle"), id("growl")]);
+    }
+
+    #[test]
+    fn learn_into_a_full_set() {
+        let movedex = movedex();
+        let mut set = set(&movedex, &["tackle", "growl", "ember", "gust"]);
+        let pending = match set.learn(movedex.get(&id("swift"))) {
+            LearnResult::MustReplace(pending) => pending,
+            LearnResult::Learned => panic!("learned a fifth move"),
+        };
+        assert_eq!(pending.id, id("swift"));
+        assert_eq!(set.len(), 4);
+
+        assert!(set.replace(4, pending).is_none());
+        assert_eq!(set.replace(1, pending).map(|m| m.m.id), Some(id("growl")));
+        assert_eq!(ids(&set), vec![id("tackle"), id("swift"), id("ember"), id("gust")]);
+        assert_eq!(set[1].pp, pending.pp);
+    }
+
+    #[test]
+    fn reorder_moves() {
+        let movedex = movedex();
+        let mut set = set(&movedex, &["tackle", "growl", "ember", "gust"]);
+        set.swap(0, 3);
+        assert_eq!(ids(&set), vec![id("gust"), id("growl"), id("ember"), id("tackle")]);
+
+        set.move_to(0, 2);
+        assert_eq!(ids(&set), vec![id("growl"), id("ember"), id("gust"), id("tackle")]);
+        set.move_to(3, 0);
+        assert_eq!(ids(&set), vec![id("tackle"), id("growl"), id("ember"), id("gust")]);
+
+        // out of range indexes do nothing
+        set.swap(0, 4);
+        set.move_to(4, 0);
+        assert_eq!(ids(&set), vec![id("tackle"), id("growl"), id("ember"), id("gust")]);
+    }
+}
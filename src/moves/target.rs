@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 /// The target of the move.
-#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
 pub enum MoveTarget {
     Any,
     Ally,
@@ -15,11 +15,6 @@ pub enum MoveTarget {
     RandomOpponent,
     AllOtherPokemon,
     AllPokemon,
+    #[default]
     None,
-}
-
-impl Default for MoveTarget {
-    fn default() -> Self {
-        Self::None
-    }
 }
\ No newline at end of file
@@ -1,13 +1,14 @@
 use core::{
     fmt::{Display, Formatter, Result as FmtResult},
-    ops::Range,
+    ops::{Range, RangeInclusive},
 };
+use hashbrown::HashMap;
 use rand::Rng;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::{
     moves::{MoveCategory, MoveId, MoveSet, OwnedIdMove},
-    types::{Effective, PokemonType},
+    types::{Effective, PokemonType, TypeChart},
     Dex, Identifiable, IdRef,
 };
 
@@ -17,15 +18,36 @@ pub use owned::*;
 mod data;
 pub use data::*;
 
+mod nature;
+pub use nature::*;
+
+mod builder;
+pub use builder::*;
+
+mod evolution;
+pub use evolution::*;
+
 pub mod stat;
-use self::stat::{BaseStat, Stat, StatType, Stats};
+
+pub mod party;
+pub use party::{Party, PARTY_LENGTH};
+use self::stat::{BaseStat, Stat, StatFormula, StatType, Stats};
 
 pub type PokemonId = u16;
 pub type Level = u8;
 pub type Experience = u32;
 pub type Friendship = u8;
+pub type Affection = u8;
 pub type Health = stat::BaseStat;
 
+pub const LEVEL_RANGE: RangeInclusive<Level> = 1..=100;
+
+/// Deserializes a level, clamping it to [LEVEL_RANGE].
+pub(crate) fn deserialize_level<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Level, D::Error> {
+    Level::deserialize(deserializer)
+        .map(|level| level.clamp(*LEVEL_RANGE.start(), *LEVEL_RANGE.end()))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pokemon {
     pub id: PokemonId,
@@ -42,17 +64,55 @@ pub struct Pokemon {
     pub weight: u16,
     pub training: Training,
     pub breeding: Breeding,
-}
 
-pub const PARTY_LENGTH: usize = 6;
+    /// The pokemon this pokemon can evolve into. Can be deserialized from a single evolution or a list.
+    #[serde(default, alias = "evolution", deserialize_with = "evolution::deserialize_evolutions")]
+    pub evolutions: Vec<Evolution>,
+
+    #[serde(default)]
+    pub rarity: Rarity,
 
-pub type Party<P> = arrayvec::ArrayVec<[P; PARTY_LENGTH]>;
+    /// Game specific data (such as cries or sprites) that is kept but not used by this crate.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra: HashMap<String, String>,
+}
 
 pub type PokemonRef<'d> = IdRef<'d, Pokemon>;
 
 pub type Pokedex = Dex<Pokemon>;
 
 impl Pokemon {
+    /// Creates a pokemon with no secondary type, moves, or base stats.
+    /// Other fields can be set with struct update syntax.
+    pub fn new(id: PokemonId, name: String, primary_type: PokemonType) -> Self {
+        Self {
+            id,
+            name,
+            primary_type,
+            secondary_type: Default::default(),
+            moves: Default::default(),
+            base: Default::default(),
+            species: Default::default(),
+            height: Default::default(),
+            weight: Default::default(),
+            training: Default::default(),
+            breeding: Default::default(),
+            evolutions: Default::default(),
+            rarity: Default::default(),
+            extra: Default::default(),
+        }
+    }
+
+    /// Starts building a pokemon with no secondary type, moves, or base stats.
+    pub fn builder(id: PokemonId, name: String, primary_type: PokemonType) -> PokemonBuilder {
+        PokemonBuilder::new(id, name, primary_type)
+    }
+
+    /// A placeholder for [Pokemon::UNKNOWN].
+    pub fn unknown() -> Self {
+        Self::new(Self::UNKNOWN, "Unknown".to_owned(), PokemonType::Normal)
+    }
+
     pub fn generate_moves(&self, level: Level) -> MoveSet<OwnedIdMove> {
         let mut learnable = self
             .moves
@@ -86,6 +146,10 @@ impl Pokemon {
         )
     }
 
+    pub fn has_type(&self, pokemon_type: PokemonType) -> bool {
+        self.primary_type == pokemon_type || self.secondary_type == Some(pokemon_type)
+    }
+
     pub fn effective(&self, user: PokemonType, category: MoveCategory) -> Effective {
         let primary = user.effective(self.primary_type, category);
         if let Some(secondary) = self.secondary_type {
@@ -95,6 +159,16 @@ impl Pokemon {
         }
     }
 
+    /// How effective a move is against this pokemon using a custom [TypeChart].
+    pub fn effective_with(&self, chart: &TypeChart, user: PokemonType, category: MoveCategory) -> Effective {
+        let primary = chart.effective(user, self.primary_type, category);
+        if let Some(secondary) = self.secondary_type {
+            primary * chart.effective(user, secondary, category)
+        } else {
+            primary
+        }
+    }
+
     pub fn exp_from(&self, level: Level) -> Experience {
         ((self.training.base_exp * level as u16) / 7) as Experience
     }
@@ -114,24 +188,67 @@ impl Pokemon {
             .flat_map(move |level| self.moves_at_level(level))
     }
 
-    pub fn stat(&self, ivs: &Stats, evs: &Stats, level: Level, stat: StatType) -> BaseStat {
+    pub fn stat(
+        &self,
+        ivs: &Stats,
+        evs: &Stats,
+        level: Level,
+        nature: Nature,
+        stat: StatType,
+    ) -> BaseStat {
+        self.stat_with(StatFormula::default(), ivs, evs, level, nature, stat)
+    }
+
+    pub fn stat_with(
+        &self,
+        formula: StatFormula,
+        ivs: &Stats,
+        evs: &Stats,
+        level: Level,
+        nature: Nature,
+        stat: StatType,
+    ) -> BaseStat {
         match stat {
-            StatType::Health => Self::base_hp(self.base.hp, ivs.hp, evs.hp, level),
-            stat => Self::base_stat(self.base.get(stat), ivs.get(stat), evs.get(stat), level),
+            StatType::Health => formula.hp(self.base.hp, ivs.hp, evs.hp, level),
+            stat => {
+                let value = formula.stat(self.base.get(stat), ivs.get(stat), evs.get(stat), level);
+                match formula {
+                    // natures were added in generation 3
+                    StatFormula::Gen1 => value,
+                    StatFormula::Gen3 => nature.apply(stat, value),
+                }
+            }
         }
     }
 
     pub fn base_stat(base: Stat, iv: Stat, ev: Stat, level: Level) -> BaseStat {
-        //add item check
-        let nature = 1.0;
-        (((2.0 * base as f32 + iv as f32 + ev as f32) * level as f32 / 100.0 + 5.0).floor()
-            * nature)
-            .floor() as BaseStat
+        StatFormula::default().stat(base, iv, ev, level)
     }
 
     pub fn base_hp(base: Stat, iv: Stat, ev: Stat, level: Level) -> BaseStat {
-        ((2.0 * base as f32 + iv as f32 + ev as f32) * level as f32 / 100.0 + level as f32 + 10.0)
-            .floor() as BaseStat
+        StatFormula::default().hp(base, iv, ev, level)
+    }
+
+    pub fn is_legendary(&self) -> bool {
+        matches!(self.rarity, Rarity::Legendary | Rarity::Mythical)
+    }
+
+    /// Legendary, mythical and ultra beast pokemon cannot breed.
+    pub fn can_breed(&self) -> bool {
+        self.rarity == Rarity::Normal
+    }
+
+    /// Checks if this pokemon can learn a move from its egg moves,
+    /// through a parent that shares an egg group and learns the move by leveling up.
+    pub fn learnable_by_breeding(&self, id: &MoveId, pokedex: &Pokedex) -> bool {
+        self.breeding.egg_moves.contains(id)
+            && pokedex.iter().any(|parent| {
+                parent.id != self.id
+                    && parent.moves.iter().any(|learnable| &learnable.1 == id)
+                    && parent.breeding.groups.iter().any(|group| {
+                        group != &EggGroup::Undiscovered && self.breeding.groups.contains(group)
+                    })
+            })
     }
 
     pub const fn default_friendship() -> Friendship {
@@ -154,3 +271,98 @@ impl Display for Pokemon {
         write!(f, "#{} {}", self.id, self.name)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{EggGroup, GrowthRate, LearnableMove, Pokedex, Pokemon, Rarity};
+    use crate::{
+        testing::{id, pokedex, BULBASAUR, CHARMANDER, PIDGEY},
+        types::PokemonType,
+        Identifiable,
+    };
+
+    #[test]
+    fn legendary_pokemon_can_not_breed() {
+        let pokedex = pokedex();
+        let pidgey = pokedex.get(&PIDGEY);
+        assert_eq!(pidgey.rarity, Rarity::Normal);
+        assert!(!pidgey.is_legendary());
+        assert!(pidgey.can_breed());
+
+        for (rarity, legendary) in [
+            (Rarity::Legendary, true),
+            (Rarity::Mythical, true),
+            (Rarity::Ultra, false),
+        ] {
+            let pokemon = Pokemon {
+                rarity,
+                ..pidgey.value().clone()
+            };
+            assert_eq!(pokemon.is_legendary(), legendary);
+            assert!(!pokemon.can_breed());
+        }
+    }
+
+    #[test]
+    fn learn_egg_moves_from_compatible_parents() {
+        let mut pokedex = pokedex();
+        let groups = |pokedex: &mut Pokedex, pokemon, groups: &[EggGroup]| {
+            pokedex.inner_mut().get_mut(&pokemon).unwrap().breeding.groups = groups.to_vec()
+        };
+        groups(&mut pokedex, BULBASAUR, &[EggGroup::Monster, EggGroup::Grass]);
+        groups(&mut pokedex, CHARMANDER, &[EggGroup::Monster, EggGroup::Dragon]);
+        pokedex.inner_mut().get_mut(&BULBASAUR).unwrap().breeding.egg_moves = vec![id("ember"), id("gust")];
+
+        let bulbasaur = pokedex.get(&BULBASAUR);
+        assert!(bulbasaur.learnable_by_breeding(&id("ember"), &pokedex));
+        // pidgey learns gust but does not share an egg group
+        assert!(!bulbasaur.learnable_by_breeding(&id("gust"), &pokedex));
+        // charmander learns scratch but it is not an egg move
+        assert!(!bulbasaur.learnable_by_breeding(&id("scratch"), &pokedex));
+
+        groups(&mut pokedex, BULBASAUR, &[EggGroup::Undiscovered]);
+        groups(&mut pokedex, CHARMANDER, &[EggGroup::Undiscovered]);
+        assert!(!pokedex.get(&BULBASAUR).learnable_by_breeding(&id("ember"), &pokedex));
+    }
+
+    #[test]
+    fn new_species_has_defaults() {
+        let pokemon = Pokemon::builder(PIDGEY, "Pidgey".to_owned(), PokemonType::Normal).build();
+        assert_eq!(pokemon.to_string(), "#16 Pidgey");
+        assert_eq!(pokemon.secondary_type, None);
+        assert!(pokemon.moves.is_empty());
+        assert!(pokemon.evolutions.is_empty());
+        assert_eq!(pokemon.training.growth_rate, GrowthRate::default());
+        assert_eq!(pokemon.training.base_friendship, 70);
+        assert_eq!(pokemon.breeding.gender, None);
+        assert_eq!(pokemon.rarity, Rarity::Normal);
+        assert_eq!(Pokemon::unknown().id, Pokemon::UNKNOWN);
+    }
+
+    #[test]
+    fn builder_setters() {
+        let pokemon = Pokemon::builder(PIDGEY, "Pidgey".to_owned(), PokemonType::Normal)
+            .secondary_type(PokemonType::Flying)
+            .moves(vec![LearnableMove(1, id("tackle"))])
+            .weight(18)
+            .rarity(Rarity::Mythical)
+            .build();
+        assert!(pokemon.has_type(PokemonType::Flying));
+        assert_eq!(pokemon.moves, vec![LearnableMove(1, id("tackle"))]);
+        assert_eq!(pokemon.weight, 18);
+        assert_eq!(pokemon.rarity, Rarity::Mythical);
+        assert_eq!(pokemon.training.base_friendship, 70);
+    }
+
+    #[test]
+    fn extra_data_round_trip() {
+        let pokedex = pokedex();
+        let mut pidgey = pokedex.get(&PIDGEY).value().clone();
+        pidgey.extra.insert("cry".to_owned(), "pidgey.ogg".to_owned());
+
+        let json = serde_json::to_string(&pidgey).unwrap();
+        let pidgey: Pokemon = serde_json::from_str(&json).unwrap();
+        assert_eq!(pidgey.extra.len(), 1);
+        assert_eq!(pidgey.extra.get("cry").map(String::as_str), Some("pidgey.ogg"));
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{
+    pokemon::{stat::Stats, Breeding, Evolution, LearnableMove, Pokemon, PokemonId, Rarity, Training},
+    types::PokemonType,
+};
+
+/// Builds a [Pokemon], starting with no secondary type, moves, or base stats.
+#[derive(Debug, Clone)]
+pub struct PokemonBuilder(Pokemon);
+
+impl PokemonBuilder {
+    pub fn new(id: PokemonId, name: String, primary_type: PokemonType) -> Self {
+        Self(Pokemon::new(id, name, primary_type))
+    }
+
+    pub fn secondary_type(mut self, secondary_type: PokemonType) -> Self {
+        self.0.secondary_type = Some(secondary_type);
+        self
+    }
+
+    pub fn moves(mut self, moves: Vec<LearnableMove>) -> Self {
+        self.0.moves = moves;
+        self
+    }
+
+    pub fn base(mut self, base: Stats) -> Self {
+        self.0.base = base;
+        self
+    }
+
+    pub fn species(mut self, species: String) -> Self {
+        self.0.species = species;
+        self
+    }
+
+    pub fn height(mut self, height: u8) -> Self {
+        self.0.height = height;
+        self
+    }
+
+    pub fn weight(mut self, weight: u16) -> Self {
+        self.0.weight = weight;
+        self
+    }
+
+    pub fn training(mut self, training: Training) -> Self {
+        self.0.training = training;
+        self
+    }
+
+    pub fn breeding(mut self, breeding: Breeding) -> Self {
+        self.0.breeding = breeding;
+        self
+    }
+
+    pub fn evolutions(mut self, evolutions: Vec<Evolution>) -> Self {
+        self.0.evolutions = evolutions;
+        self
+    }
+
+    pub fn rarity(mut self, rarity: Rarity) -> Self {
+        self.0.rarity = rarity;
+        self
+    }
+
+    pub fn build(self) -> Pokemon {
+        self.0
+    }
+}
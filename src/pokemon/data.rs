@@ -1,9 +1,13 @@
-use core::ops::Range;
+use core::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    ops::{Range, RangeInclusive},
+};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     moves::MoveId,
-    pokemon::{Level, Experience},
+    pokemon::{stat::Stats, Experience, Friendship, Level, Pokemon, LEVEL_RANGE},
 };
 
 
@@ -15,6 +19,101 @@ pub enum Gender {
 
 impl Gender {
     pub(crate) const RANGE: Range<u8> = 0..8;
+
+    pub const fn symbol(&self) -> &'static str {
+        match self {
+            Gender::Male => "♂",
+            Gender::Female => "♀",
+        }
+    }
+}
+
+impl Display for Gender {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.symbol())
+    }
+}
+
+/// Pokerus infection status. Infected pokemon gain double EVs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum Pokerus {
+    #[default]
+    None,
+    Infected { days_left: u8 },
+    /// A cured pokemon can not be infected again.
+    Cured,
+}
+
+impl Pokerus {
+    pub(crate) const DAYS: RangeInclusive<u8> = 1..=4;
+    pub(crate) const INFECT_CHANCE: u32 = 3;
+
+    pub fn infected(random: &mut impl Rng) -> Self {
+        Self::Infected {
+            days_left: random.gen_range(Self::DAYS),
+        }
+    }
+
+    pub fn is_infected(&self) -> bool {
+        matches!(self, Self::Infected { .. })
+    }
+
+    /// Passes a day of the infection, curing it when it runs out.
+    pub fn tick(&mut self) {
+        if let Self::Infected { days_left } = self {
+            *days_left = days_left.saturating_sub(1);
+            if *days_left == 0 {
+                *self = Self::Cured;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Rarity {
+    #[default]
+    Normal,
+    Legendary,
+    Mythical,
+    /// Ultra Beasts
+    Ultra,
+}
+
+pub type Scale = u8;
+
+/// The size of a pokemon compared to others of its species.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum SizeMark {
+    XXS,
+    XS,
+    S,
+    M,
+    L,
+    XL,
+    XXL,
+}
+
+impl SizeMark {
+    pub const fn from_scale(scale: Scale) -> Self {
+        match scale {
+            0 => Self::XXS,
+            1..=30 => Self::XS,
+            31..=60 => Self::S,
+            61..=194 => Self::M,
+            195..=224 => Self::L,
+            225..=254 => Self::XL,
+            255 => Self::XXL,
+        }
+    }
+
+    /// The multiplier applied to the species' height and weight (0.8 - 1.2).
+    pub fn multiplier(scale: Scale) -> f32 {
+        0.8 + 0.4 * scale as f32 / Scale::MAX as f32
+    }
+
+    pub const fn default_scale() -> Scale {
+        128
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -25,53 +124,200 @@ pub struct Training {
     pub base_exp: u16,
     #[serde(default)]
     pub growth_rate: GrowthRate,
-    //pub ev_yield: Option<(String, usize)>,
+    /// EVs gained by defeating this pokemon.
+    #[serde(default)]
+    pub ev_yield: Stats,
     //pub catch_rate: Option<u8>,
-    //pub base_friendship: Option<u8>,
+    #[serde(default = "Pokemon::default_friendship")]
+    pub base_friendship: Friendship,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+impl Default for Training {
+    fn default() -> Self {
+        Self {
+            base_exp: Default::default(),
+            growth_rate: Default::default(),
+            ev_yield: Default::default(),
+            base_friendship: Pokemon::default_friendship(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub struct Breeding {
-    // pub groups: Vec<EggGroup>,
+    #[serde(default)]
+    pub groups: Vec<EggGroup>,
     /// Pokemon gender chance: None = no gender, 0 = 100% female, 7 = 100% male (0-8 scale)
     pub gender: Option<u8>,
+    /// Moves this pokemon can learn from a parent when hatched from an egg.
+    #[serde(default)]
+    pub egg_moves: Vec<MoveId>,
     // pub cycles: Option<u8>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EggGroup {
+    Monster,
+    Water1,
+    Bug,
+    Flying,
+    Field,
+    Fairy,
+    Grass,
+    HumanLike,
+    Water3,
+    Mineral,
+    Amorphous,
+    Water2,
+    Ditto,
+    Dragon,
+    /// Pokemon in this group cannot breed.
+    Undiscovered,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum GrowthRate {
-    Slow,
     Fast,
-    Medium,
+    #[serde(alias = "Medium")]
+    MediumFast,
+    #[default]
     MediumSlow,
-    FastThenVerySlow,
-    SlowThenVeryFast,
-}
-
-impl Default for GrowthRate {
-    fn default() -> Self {
-        Self::MediumSlow
-    }
+    Slow,
+    #[serde(alias = "SlowThenVeryFast")]
+    Erratic,
+    #[serde(alias = "FastThenVerySlow")]
+    Fluctuating,
 }
 
 impl GrowthRate {
+    /// The total experience a pokemon needs to reach a level.
     pub fn max_exp(self, level: Level) -> Experience {
-        (match level as i32 {
-            0 => 0,
-            1 => 1,
-            level => match self {
-                GrowthRate::Slow => 5 * (level.pow(3) >> 2),
-                GrowthRate::Fast => (level.pow(3) << 2) / 5,
-                GrowthRate::Medium => level.pow(3),
-                GrowthRate::MediumSlow => {
-                    (6 * level.pow(3)) / 5 - (15 * level.pow(2)) + (100 * level) - 140
-                }
-                _ => {
-                    (1.2 * level.pow(3) as f32) as i32 - 15 * level.pow(2) as i32
-                        + 100 * level as i32
-                        - 140
-                } // MediumSlow
+        let n = level as i64;
+        let cube = n.pow(3);
+        (match level {
+            0 | 1 => 0,
+            _ => match self {
+                GrowthRate::Fast => 4 * cube / 5,
+                GrowthRate::MediumFast => cube,
+                GrowthRate::MediumSlow => 6 * cube / 5 - 15 * n.pow(2) + 100 * n - 140,
+                GrowthRate::Slow => 5 * cube / 4,
+                GrowthRate::Erratic => match level {
+                    0..=49 => cube * (100 - n) / 50,
+                    50..=67 => cube * (150 - n) / 100,
+                    68..=97 => cube * ((1911 - 10 * n) / 3) / 500,
+                    _ => cube * (160 - n) / 100,
+                },
+                GrowthRate::Fluctuating => match level {
+                    0..=14 => cube * ((n + 1) / 3 + 24) / 50,
+                    15..=35 => cube * (n + 14) / 50,
+                    _ => cube * (n / 2 + 32) / 50,
+                },
             },
         }) as Experience
     }
+
+    /// The experience a pokemon needs to go from a level to the next one, or 0 at level 100.
+    pub fn level_exp(self, level: Level) -> Experience {
+        match level < *LEVEL_RANGE.end() {
+            true => self.max_exp(level + 1) - self.max_exp(level),
+            false => 0,
+        }
+    }
+
+    /// The level a pokemon with this much total experience is at, up to level 100.
+    pub fn level_from_exp(self, experience: Experience) -> Level {
+        LEVEL_RANGE
+            .rev()
+            .find(|level| self.max_exp(*level) <= experience)
+            .unwrap_or(*LEVEL_RANGE.start())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Experience, Gender, GrowthRate, SizeMark};
+
+    #[test]
+    fn gender_symbols() {
+        assert_eq!(Gender::Male.symbol(), "♂");
+        assert_eq!(Gender::Female.symbol(), "♀");
+        assert_eq!(Gender::Female.to_string(), "♀");
+        // pokemon without a gender show nothing
+        assert_eq!(None::<Gender>.map(|g| g.symbol()).unwrap_or_default(), "");
+    }
+
+    #[test]
+    fn size_marks_from_scale() {
+        assert_eq!(SizeMark::from_scale(0), SizeMark::XXS);
+        assert_eq!(SizeMark::from_scale(30), SizeMark::XS);
+        assert_eq!(SizeMark::from_scale(SizeMark::default_scale()), SizeMark::M);
+        assert_eq!(SizeMark::from_scale(225), SizeMark::XL);
+        assert_eq!(SizeMark::from_scale(255), SizeMark::XXL);
+
+        assert!((SizeMark::multiplier(0) - 0.8).abs() < f32::EPSILON);
+        assert!((SizeMark::multiplier(255) - 1.2).abs() < f32::EPSILON);
+        assert!((SizeMark::multiplier(SizeMark::default_scale()) - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn growth_rates_at_level_100() {
+        assert_eq!(GrowthRate::Fast.max_exp(100), 800_000);
+        assert_eq!(GrowthRate::MediumFast.max_exp(100), 1_000_000);
+        assert_eq!(GrowthRate::MediumSlow.max_exp(100), 1_059_860);
+        assert_eq!(GrowthRate::Slow.max_exp(100), 1_250_000);
+        assert_eq!(GrowthRate::Erratic.max_exp(100), 600_000);
+        assert_eq!(GrowthRate::Fluctuating.max_exp(100), 1_640_000);
+    }
+
+    #[test]
+    fn growth_rate_table_values() {
+        for rate in [GrowthRate::Fast, GrowthRate::MediumSlow, GrowthRate::Erratic, GrowthRate::Fluctuating] {
+            assert_eq!(rate.max_exp(1), 0);
+        }
+        assert_eq!(GrowthRate::MediumSlow.max_exp(2), 9);
+        assert_eq!(GrowthRate::MediumSlow.max_exp(50), 117_360);
+        assert_eq!(GrowthRate::Erratic.max_exp(50), 125_000);
+        assert_eq!(GrowthRate::Erratic.max_exp(68), 257_834);
+        assert_eq!(GrowthRate::Fluctuating.max_exp(15), 1_957);
+        assert_eq!(GrowthRate::Fluctuating.max_exp(50), 142_500);
+        assert_eq!(GrowthRate::Slow.max_exp(50), 156_250);
+    }
+
+    #[test]
+    fn growth_rate_aliases() {
+        assert_eq!(serde_json::from_str::<GrowthRate>("\"Medium\"").unwrap(), GrowthRate::MediumFast);
+        assert_eq!(
+            serde_json::from_str::<GrowthRate>("\"SlowThenVeryFast\"").unwrap(),
+            GrowthRate::Erratic
+        );
+        assert_eq!(GrowthRate::default(), GrowthRate::MediumSlow);
+    }
+
+    #[test]
+    fn level_from_exp() {
+        let rate = GrowthRate::MediumFast;
+        assert_eq!(rate.level_from_exp(0), 1);
+        assert_eq!(rate.level_from_exp(7), 1);
+        // exactly on a threshold is the higher level
+        assert_eq!(rate.level_from_exp(8), 2);
+        assert_eq!(rate.level_from_exp(124_999), 49);
+        assert_eq!(rate.level_from_exp(125_000), 50);
+        assert_eq!(rate.level_from_exp(1_000_000), 100);
+        assert_eq!(rate.level_from_exp(Experience::MAX), 100);
+
+        for rate in [GrowthRate::Erratic, GrowthRate::Fluctuating] {
+            for level in [1, 15, 36, 50, 68, 98, 100] {
+                assert_eq!(rate.level_from_exp(rate.max_exp(level)), level);
+            }
+        }
+    }
+
+    #[test]
+    fn level_exp() {
+        let rate = GrowthRate::MediumFast;
+        assert_eq!(rate.level_exp(1), 8);
+        assert_eq!(rate.level_exp(2), 19);
+        assert_eq!(rate.level_exp(99), 1_000_000 - 970_299);
+        assert_eq!(rate.level_exp(100), 0);
+    }
 }
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{
+    item::ItemId,
+    pokemon::{Friendship, Level, PokemonId},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct Evolution {
+    /// The pokemon this pokemon evolves into.
+    pub into: PokemonId,
+    #[serde(rename = "type")]
+    pub kind: EvolutionType,
+}
+
+/// The condition a pokemon needs to meet to evolve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum EvolutionType {
+    /// Evolves when leveling up to at least this level.
+    Level(Level),
+    /// Evolves when this item (such as an evolution stone) is used on the pokemon.
+    Stone(ItemId),
+    /// Evolves when leveling up while holding this item.
+    HeldItem(ItemId),
+    /// Evolves when traded, optionally while holding this item.
+    Trade(Option<ItemId>),
+    /// Evolves when leveling up with at least this much friendship (usually 220).
+    Friendship(Friendship),
+}
+
+/// The event that may cause a pokemon to evolve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvolutionTrigger {
+    LevelUp,
+    /// An item is used on the pokemon.
+    Item(ItemId),
+    Trade,
+}
+
+impl Evolution {
+    /// Checks if the evolution's condition is met.
+    /// `level`, `friendship` and `held` are the level, friendship and held item of the pokemon that would evolve.
+    pub fn matches(
+        &self,
+        trigger: &EvolutionTrigger,
+        level: Level,
+        friendship: Friendship,
+        held: Option<&ItemId>,
+    ) -> bool {
+        match (&self.kind, trigger) {
+            (EvolutionType::Level(target), EvolutionTrigger::LevelUp) => level >= *target,
+            (EvolutionType::Stone(stone), EvolutionTrigger::Item(used)) => stone == used,
+            (EvolutionType::HeldItem(item), EvolutionTrigger::LevelUp) => held == Some(item),
+            // The threshold is inclusive, so a pokemon with exactly this much friendship evolves.
+            (EvolutionType::Friendship(threshold), EvolutionTrigger::LevelUp) => {
+                friendship >= *threshold
+            }
+            (EvolutionType::Trade(item), EvolutionTrigger::Trade) => {
+                item.as_ref().map(|item| held == Some(item)).unwrap_or(true)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Deserializes either a single evolution or a list of evolutions.
+pub(crate) fn deserialize_evolutions<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Evolution>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Evolutions {
+        Many(Vec<Evolution>),
+        One(Option<Evolution>),
+    }
+
+    Evolutions::deserialize(deserializer).map(|evolutions| match evolutions {
+        Evolutions::Many(evolutions) => evolutions,
+        Evolutions::One(evolution) => evolution.into_iter().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Evolution, EvolutionTrigger, EvolutionType};
+    use crate::testing::{id, GRAVELER};
+
+    fn evolution(kind: EvolutionType) -> Evolution {
+        Evolution { into: GRAVELER, kind }
+    }
+
+    #[test]
+    fn level_evolutions() {
+        let level = evolution(EvolutionType::Level(25));
+        assert!(!level.matches(&EvolutionTrigger::LevelUp, 24, 0, None));
+        assert!(level.matches(&EvolutionTrigger::LevelUp, 25, 0, None));
+        assert!(!level.matches(&EvolutionTrigger::Trade, 30, 0, None));
+    }
+
+    #[test]
+    fn item_evolutions() {
+        let stone = evolution(EvolutionType::Stone(id("fire_stone")));
+        assert!(stone.matches(&EvolutionTrigger::Item(id("fire_stone")), 1, 0, None));
+        assert!(!stone.matches(&EvolutionTrigger::Item(id("water_stone")), 1, 0, None));
+        assert!(!stone.matches(&EvolutionTrigger::LevelUp, 100, 0, None));
+
+        let held = evolution(EvolutionType::HeldItem(id("kings_rock")));
+        assert!(held.matches(&EvolutionTrigger::LevelUp, 1, 0, Some(&id("kings_rock"))));
+        assert!(!held.matches(&EvolutionTrigger::LevelUp, 1, 0, Some(&id("potion"))));
+        assert!(!held.matches(&EvolutionTrigger::LevelUp, 1, 0, None));
+    }
+
+    #[test]
+    fn friendship_evolutions() {
+        let friendship = evolution(EvolutionType::Friendship(220));
+        assert!(!friendship.matches(&EvolutionTrigger::LevelUp, 1, 219, None));
+        assert!(friendship.matches(&EvolutionTrigger::LevelUp, 1, 220, None));
+        assert!(friendship.matches(&EvolutionTrigger::LevelUp, 1, 255, None));
+        assert!(!friendship.matches(&EvolutionTrigger::Trade, 1, 255, None));
+    }
+
+    #[test]
+    fn evolutions_from_one_or_many() {
+        #[derive(serde::Deserialize)]
+        struct Species {
+            #[serde(default, alias = "evolution", deserialize_with = "super::deserialize_evolutions")]
+            evolutions: Vec<Evolution>,
+        }
+        let species = |json| serde_json::from_str::<Species>(json).unwrap().evolutions;
+
+        let stone = evolution(EvolutionType::Stone(id("fire_stone")));
+        assert_eq!(
+            species(r#"{ "evolution": { "into": 75, "type": { "Stone": "fire_stone" } } }"#),
+            vec![stone]
+        );
+        assert_eq!(
+            species(r#"{ "evolutions": [{ "into": 75, "type": { "Stone": "fire_stone" } }, { "into": 75, "type": { "Level": 25 } }] }"#),
+            vec![stone, evolution(EvolutionType::Level(25))]
+        );
+        assert!(species(r#"{ "evolution": null }"#).is_empty());
+        assert!(species("{}").is_empty());
+    }
+}
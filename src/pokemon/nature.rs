@@ -0,0 +1,169 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::pokemon::stat::{BaseStat, StatType};
+
+/// A pokemon's nature increases one stat and decreases another.
+/// Natures that increase and decrease the same stat are neutral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+pub enum Nature {
+    #[default]
+    Hardy,
+    Lonely,
+    Brave,
+    Adamant,
+    Naughty,
+    Bold,
+    Docile,
+    Relaxed,
+    Impish,
+    Lax,
+    Timid,
+    Hasty,
+    Serious,
+    Jolly,
+    Naive,
+    Modest,
+    Mild,
+    Quiet,
+    Bashful,
+    Rash,
+    Calm,
+    Gentle,
+    Sassy,
+    Careful,
+    Quirky,
+}
+
+impl Nature {
+    pub const ALL: [Nature; 25] = [
+        Nature::Hardy,
+        Nature::Lonely,
+        Nature::Brave,
+        Nature::Adamant,
+        Nature::Naughty,
+        Nature::Bold,
+        Nature::Docile,
+        Nature::Relaxed,
+        Nature::Impish,
+        Nature::Lax,
+        Nature::Timid,
+        Nature::Hasty,
+        Nature::Serious,
+        Nature::Jolly,
+        Nature::Naive,
+        Nature::Modest,
+        Nature::Mild,
+        Nature::Quiet,
+        Nature::Bashful,
+        Nature::Rash,
+        Nature::Calm,
+        Nature::Gentle,
+        Nature::Sassy,
+        Nature::Careful,
+        Nature::Quirky,
+    ];
+
+    /// The stats a nature can affect, in the order natures are listed.
+    const STATS: [StatType; 5] = [
+        StatType::Attack,
+        StatType::Defense,
+        StatType::Speed,
+        StatType::SpAttack,
+        StatType::SpDefense,
+    ];
+
+    pub fn random(random: &mut impl Rng) -> Self {
+        Self::ALL[random.gen_range(0..Self::ALL.len())]
+    }
+
+    pub const fn increased(&self) -> StatType {
+        Self::STATS[*self as usize / Self::STATS.len()]
+    }
+
+    pub const fn decreased(&self) -> StatType {
+        Self::STATS[*self as usize % Self::STATS.len()]
+    }
+
+    pub fn neutral(&self) -> bool {
+        self.increased() == self.decreased()
+    }
+
+    /// The multiplier this nature applies to a stat. Health is never affected.
+    pub fn multiplier(&self, stat: StatType) -> f32 {
+        match stat {
+            StatType::Health => 1.0,
+            _ if self.neutral() => 1.0,
+            stat if stat == self.increased() => 1.1,
+            stat if stat == self.decreased() => 0.9,
+            _ => 1.0,
+        }
+    }
+
+    /// Applies this nature's multiplier to a stat, rounding down like the games do.
+    pub fn apply(&self, stat: StatType, value: BaseStat) -> BaseStat {
+        let tenths = (self.multiplier(stat) * 10.0).round() as u32;
+        (value as u32 * tenths / 10) as BaseStat
+    }
+
+    /// Get the nature that increases one stat and decreases another.
+    /// Returns [None] if both stats are the same or either cannot be affected by a nature.
+    pub fn recommend(boost: StatType, hinder: StatType) -> Option<Self> {
+        if boost == hinder {
+            return None;
+        }
+        let position = |stat| Self::STATS.iter().position(|s| s == &stat);
+        let (boost, hinder) = (position(boost)?, position(hinder)?);
+        Some(Self::ALL[boost * Self::STATS.len() + hinder])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Nature;
+    use crate::pokemon::stat::StatType;
+
+    #[test]
+    fn recommend_natures() {
+        assert_eq!(
+            Nature::recommend(StatType::Attack, StatType::SpAttack),
+            Some(Nature::Adamant)
+        );
+        assert_eq!(
+            Nature::recommend(StatType::Speed, StatType::Attack),
+            Some(Nature::Timid)
+        );
+        assert_eq!(Nature::recommend(StatType::Attack, StatType::Attack), None);
+        assert_eq!(Nature::recommend(StatType::Health, StatType::Attack), None);
+    }
+
+    #[test]
+    fn recommend_is_the_inverse_of_increased_and_decreased() {
+        for nature in Nature::ALL {
+            match nature.neutral() {
+                true => assert_eq!(Nature::recommend(nature.increased(), nature.decreased()), None),
+                false => assert_eq!(
+                    Nature::recommend(nature.increased(), nature.decreased()),
+                    Some(nature)
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn nature_multipliers() {
+        assert_eq!(Nature::Adamant.multiplier(StatType::Attack), 1.1);
+        assert_eq!(Nature::Adamant.multiplier(StatType::SpAttack), 0.9);
+        assert_eq!(Nature::Adamant.multiplier(StatType::Speed), 1.0);
+        assert_eq!(Nature::Adamant.apply(StatType::Attack, 100), 110);
+        assert_eq!(Nature::Adamant.apply(StatType::SpAttack, 105), 94);
+        for nature in Nature::ALL {
+            assert_eq!(nature.multiplier(StatType::Health), 1.0);
+            if nature.neutral() {
+                for stat in StatType::ALL {
+                    assert_eq!(nature.apply(stat, 123), 123);
+                }
+            }
+        }
+    }
+}
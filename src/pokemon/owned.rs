@@ -3,16 +3,19 @@ use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    ailment::LiveAilment,
+    ailment::{Ailment, LiveAilment},
     item::{
-        usage::{ItemAction, ItemCondition, ItemUsageKind},
-        Item, ItemId, ItemRef, Itemdex,
+        usage::{ItemAction, ItemCondition, ItemOutcome, ItemUsageKind},
+        bag::Bag,
+        Item, ItemId, ItemRef, Itemdex, StackSize,
     },
-    moves::{OwnedRefMove, MoveId, MoveRefSet, MoveSet, Movedex, OwnedIdMove, MOVESET_LENGTH, PP},
+    moves::{Move, MoveCategory, OwnedRefMove, MoveId, MoveRef, MoveRefSet, MoveSet, Movedex, OwnedIdMove, Power, MOVESET_LENGTH, PP},
     pokemon::{
-        stat::{BaseStat, StatType, Stats},
-        Experience, Friendship, Gender, Health, Level, Pokedex, Pokemon, PokemonId, PokemonRef,
+        stat::{BaseStat, StatFormula, StatSnapshot, StatType, Stats},
+        Affection, Evolution, EvolutionTrigger, EvolutionType, Experience, Friendship, Gender, GrowthRate, Health, Level, LEVEL_RANGE, Pokedex, Pokemon, PokemonId, PokemonRef, Nature, Pokerus, Scale, SizeMark,
     },
+    types::{Effective, PokemonType, TypeChart},
+    IdRef,
 };
 
 pub type OwnedIdPokemon = OwnedPokemon<PokemonId, MoveSet<OwnedIdMove>, ItemId, Option<Health>>;
@@ -24,6 +27,7 @@ pub struct OwnedPokemon<P, M, I, H> {
     pub pokemon: P,
 
     /// Level of the pokemon (1 - 100)
+    #[serde(deserialize_with = "crate::pokemon::deserialize_level")]
     pub level: Level,
 
     /// Optional nickname for the pokemon
@@ -33,6 +37,12 @@ pub struct OwnedPokemon<P, M, I, H> {
     #[serde(default)]
     pub gender: Option<Gender>,
 
+    #[serde(default)]
+    pub nature: Nature,
+
+    #[serde(default)]
+    pub shiny: bool,
+
     #[serde(default)]
     pub moves: M,
 
@@ -42,6 +52,11 @@ pub struct OwnedPokemon<P, M, I, H> {
     #[serde(default = "Option::default")]
     pub item: Option<I>,
 
+    /// The last held item the pokemon used up, which can be restored with [OwnedPokemon::recycle].
+    /// This is only kept for the current battle and is not saved.
+    #[serde(skip, default = "Option::default")]
+    pub consumed_item: Option<I>,
+
     #[serde(default)]
     pub ailment: Option<LiveAilment>,
 
@@ -50,34 +65,234 @@ pub struct OwnedPokemon<P, M, I, H> {
     #[serde(default)]
     pub evs: Stats,
 
+    /// Experience gained since reaching the current level.
     #[serde(default)]
     pub experience: Experience,
 
     #[serde(default = "Pokemon::default_friendship")]
     pub friendship: Friendship,
+
+    #[serde(default)]
+    pub affection: Affection,
+
+    #[serde(default)]
+    pub pokerus: Pokerus,
+
+    /// The size of the pokemon compared to others of its species.
+    #[serde(default = "SizeMark::default_scale")]
+    pub scale: Scale,
+
+    /// Stats given by an outside source (such as a server) that override calculated ones.
+    #[serde(skip)]
+    pub stat_snapshot: Option<StatSnapshot>,
+
+    /// The formula used to calculate the pokemon's stats. This is set by the game and is not saved.
+    #[serde(skip)]
+    pub stat_formula: StatFormula,
+}
+
+impl<P, M, I, H> OwnedPokemon<P, M, I, H> {
+    /// Cures the pokemon's ailment if it matches the given one. If no ailment is given, any ailment is cured.
+    /// Returns whether the pokemon was cured.
+    pub fn cure_ailment(&mut self, ailment: Option<&Ailment>) -> bool {
+        match &self.ailment {
+            Some(live) if ailment.map(|a| a == &live.ailment).unwrap_or(true) => {
+                self.ailment = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Infects the pokemon with pokerus if it has never had it before.
+    pub fn infect(&mut self, random: &mut impl Rng) -> bool {
+        match self.pokerus {
+            Pokerus::None => {
+                self.pokerus = Pokerus::infected(random);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Passes a day of the pokemon's pokerus infection.
+    pub fn cure_tick(&mut self) {
+        self.pokerus.tick()
+    }
+
+    /// Affection hearts (0 - 5).
+    pub fn affection_hearts(&self) -> u8 {
+        (self.affection / 50).min(5)
+    }
+
+    /// The critical hit stage bonus from affection. Only pokemon with max affection get a bonus.
+    pub fn affection_crit_bonus(&self) -> u8 {
+        (self.affection_hearts() == 5) as u8
+    }
+
+    /// Rolls whether the pokemon survives a hit that would knock it out with 1 HP.
+    /// Pokemon need at least 3 affection hearts to endure.
+    pub fn affection_endure(&self, random: &mut impl Rng) -> bool {
+        let chance = match self.affection_hearts() {
+            5 => 20,
+            4 => 15,
+            3 => 10,
+            _ => return false,
+        };
+        random.gen_range(0..100) < chance
+    }
+
+    /// Gives the pokemon back the last item it used up (Recycle).
+    /// Returns false if the pokemon is holding an item or has not used one up.
+    pub fn recycle(&mut self) -> bool {
+        match self.item.is_none() {
+            true => match self.consumed_item.take() {
+                Some(item) => {
+                    self.item = Some(item);
+                    true
+                }
+                None => false,
+            },
+            false => false,
+        }
+    }
+
+    pub fn is_shiny(&self) -> bool {
+        self.shiny
+    }
+
+    pub fn size_category(&self) -> SizeMark {
+        SizeMark::from_scale(self.scale)
+    }
+
+    /// Adds EVs to the pokemon without letting a stat go over [Stats::MAX_EV]
+    /// or the total go over [Stats::MAX_EVS_TOTAL].
+    /// Stats are filled in order until the total is reached and the rest is discarded.
+    /// Returns how many EVs were added.
+    pub fn add_evs(&mut self, gains: &Stats) -> u16 {
+        let mut remaining = Stats::MAX_EVS_TOTAL.saturating_sub(self.evs.total());
+        let mut applied = 0;
+        for stat in StatType::ALL {
+            let gain = self.evs.add_ev_clamped(stat, gains.get(stat), remaining) as u16;
+            remaining -= gain;
+            applied += gain;
+        }
+        applied
+    }
+
+    /// Raises friendship for a level gained. Pokemon gain less friendship the more they already have.
+    pub fn level_up_friendship(&mut self) {
+        let gain = match self.friendship {
+            0..=99 => 5,
+            100..=199 => 3,
+            _ => 2,
+        };
+        self.friendship = self.friendship.saturating_add(gain);
+    }
+
+    /// Lowers friendship when the pokemon faints.
+    pub fn on_faint(&mut self) {
+        self.friendship = self.friendship.saturating_sub(1);
+    }
+
+    /// Adds the EVs gained from defeating a pokemon, doubled if this pokemon has pokerus.
+    /// Returns how many EVs were added.
+    pub fn gain_evs_from(&mut self, defeated: &Pokemon) -> u16 {
+        let mut gains = defeated.training.ev_yield;
+        if self.pokerus.is_infected() {
+            for stat in StatType::ALL {
+                let ev = gains.get_mut(stat);
+                *ev = ev.saturating_mul(2);
+            }
+        }
+        self.add_evs(&gains)
+    }
 }
 
 impl OwnedIdPokemon {
+    /// The default chance of a generated pokemon being shiny, as 1 in this many.
+    pub const SHINY_ODDS: u32 = 4096;
+
     pub fn generate(
         random: &mut impl Rng,
         pokemon: PokemonId,
         level: Level,
         gender: Option<Gender>,
         ivs: Option<Stats>,
+    ) -> Self {
+        Self::generate_with_odds(random, pokemon, level, gender, ivs, Self::SHINY_ODDS)
+    }
+
+    /// Generates a pokemon with a 1 in `shiny_odds` chance of being shiny, such as for shiny charms or events.
+    pub fn generate_with_odds(
+        random: &mut impl Rng,
+        pokemon: PokemonId,
+        level: Level,
+        gender: Option<Gender>,
+        ivs: Option<Stats>,
+        shiny_odds: u32,
+    ) -> Self {
+        let ivs = ivs.unwrap_or_else(|| Stats::random(random));
+        let nature = Nature::random(random);
+        let shiny = random.gen_range(0..shiny_odds.max(1)) == 0;
+        Self {
+            scale: random.gen(),
+            ..Self::generate_fixed(pokemon, level, nature, ivs, gender, shiny)
+        }
+    }
+
+    /// Generates a pokemon with every field specified, such as for gift or static encounters.
+    pub fn generate_fixed(
+        pokemon: PokemonId,
+        level: Level,
+        nature: Nature,
+        ivs: Stats,
+        gender: Option<Gender>,
+        shiny: bool,
     ) -> Self {
         Self {
             pokemon,
             level,
             gender,
-            ivs: ivs.unwrap_or_else(|| Stats::random(random)),
+            nature,
+            shiny,
+            ivs,
             friendship: Pokemon::default_friendship(),
+            affection: Default::default(),
             hp: Default::default(),
             nickname: Default::default(),
             moves: Default::default(),
             evs: Default::default(),
             item: Default::default(),
+            consumed_item: Default::default(),
             ailment: Default::default(),
             experience: Default::default(),
+            pokerus: Default::default(),
+            scale: SizeMark::default_scale(),
+            stat_snapshot: Default::default(),
+            stat_formula: Default::default(),
+        }
+    }
+
+    /// A checksum of the saved form of the pokemon, used to detect corrupted saves.
+    pub fn checksum(&self) -> u64 {
+        crate::checksum::checksum(self)
+    }
+
+    /// Moves a saved level up to match its experience if the save holds more
+    /// experience than its level allows, keeping the total experience.
+    fn reconcile_exp(
+        growth_rate: GrowthRate,
+        level: Level,
+        experience: Experience,
+    ) -> (Level, Experience) {
+        match level < *LEVEL_RANGE.end() && experience >= growth_rate.level_exp(level) {
+            true => {
+                let total = growth_rate.max_exp(level).saturating_add(experience);
+                let level = growth_rate.level_from_exp(total);
+                (level, total - growth_rate.max_exp(level))
+            }
+            false => (level, experience),
         }
     }
 
@@ -89,13 +304,24 @@ impl OwnedIdPokemon {
         itemdex: &'d Itemdex,
     ) -> Option<OwnedRefPokemon<'d>> {
         let pokemon = pokedex.try_get(&self.pokemon)?;
-        let hp = self
-            .hp
-            .unwrap_or_else(|| pokemon.stat(&self.ivs, &self.evs, self.level, StatType::Health));
+        let (level, experience) = Self::reconcile_exp(
+            pokemon.training.growth_rate,
+            self.level,
+            self.experience,
+        );
+        let max_hp = pokemon.stat_with(
+            self.stat_formula,
+            &self.ivs,
+            &self.evs,
+            level,
+            self.nature,
+            StatType::Health,
+        );
+        let hp = self.hp.map(|hp| hp.min(max_hp)).unwrap_or(max_hp);
         let moves = MoveRefSet::new(
             movedex,
             if self.moves.is_empty() {
-                pokemon.generate_moves(self.level)
+                pokemon.generate_moves(level)
             } else {
                 self.moves
             }
@@ -103,20 +329,28 @@ impl OwnedIdPokemon {
             .flat_map(|i| i.init(movedex))
             .collect(),
         );
-        let item = self.item.map(|ref id| itemdex.try_get(id)).flatten();
+        let item = self.item.and_then(|ref id| itemdex.try_get(id));
         let gender = self.gender.or_else(|| pokemon.generate_gender(random));
         Some(OwnedRefPokemon {
             pokemon,
             nickname: self.nickname,
-            level: self.level,
+            level,
             gender,
+            nature: self.nature,
+            shiny: self.shiny,
             ivs: self.ivs,
             evs: self.evs,
-            experience: self.experience,
+            experience,
             friendship: self.friendship,
+            affection: self.affection,
+            pokerus: self.pokerus,
+            scale: self.scale,
+            stat_snapshot: self.stat_snapshot,
+            stat_formula: self.stat_formula,
             moves,
             ailment: self.ailment,
             item,
+            consumed_item: None,
             hp,
         })
     }
@@ -135,26 +369,200 @@ impl<'a> OwnedRefPokemon<'a> {
         self.stat(StatType::Health)
     }
 
+    /// The height of this pokemon, scaled from its species' height.
+    pub fn height(&self) -> f32 {
+        self.pokemon.height as f32 * SizeMark::multiplier(self.scale)
+    }
+
+    /// The weight of this pokemon, scaled from its species' weight.
+    pub fn weight(&self) -> f32 {
+        self.pokemon.weight as f32 * SizeMark::multiplier(self.scale)
+    }
+
     pub fn percent_hp(&self) -> f32 {
         self.hp() as f32 / self.max_hp() as f32
     }
 
     pub fn stat(&self, stat: StatType) -> BaseStat {
-        self.pokemon.stat(&self.ivs, &self.evs, self.level, stat)
+        match &self.stat_snapshot {
+            Some(snapshot) => snapshot.get(stat),
+            None => self.pokemon.stat_with(
+                self.stat_formula,
+                &self.ivs,
+                &self.evs,
+                self.level,
+                self.nature,
+                stat,
+            ),
+        }
+    }
+
+    pub fn snapshot_stats(&self) -> StatSnapshot {
+        let stat = |stat| self.stat(stat);
+        StatSnapshot {
+            hp: stat(StatType::Health),
+            atk: stat(StatType::Attack),
+            def: stat(StatType::Defense),
+            sp_atk: stat(StatType::SpAttack),
+            sp_def: stat(StatType::SpDefense),
+            speed: stat(StatType::Speed),
+        }
+    }
+
+    /// Uses the given stats instead of calculating them.
+    pub fn apply_stat_snapshot(&mut self, snapshot: StatSnapshot) {
+        self.stat_snapshot = Some(snapshot);
+        self.hp = self.hp.min(snapshot.hp);
+    }
+
+    /// Calculates the damage a move would deal to the target.
+    /// `random` is the damage roll, usually between 0.85 and 1.0.
+    /// Returns [None] for moves that do not deal damage.
+    pub fn damage(&self, m: &Move, target: &Self, random: f32) -> Option<Health> {
+        self.damage_effective(m, target, Self::effective(None, m, target), random)
+    }
+
+    /// Calculates damage using a custom [TypeChart].
+    pub fn damage_with(&self, chart: &TypeChart, m: &Move, target: &Self, random: f32) -> Option<Health> {
+        self.damage_effective(m, target, Self::effective(Some(chart), m, target), random)
+    }
+
+    /// How effective a move is against the target, using the standard type chart if no chart is given.
+    fn effective(chart: Option<&TypeChart>, m: &Move, target: &Self) -> Effective {
+        match chart {
+            Some(chart) => target.pokemon.effective_with(chart, m.pokemon_type, m.category),
+            None => target.pokemon.effective(m.pokemon_type, m.category),
+        }
+    }
+
+    fn damage_effective(&self, m: &Move, target: &Self, effective: Effective, random: f32) -> Option<Health> {
+        if m.category == MoveCategory::Status {
+            return None;
+        }
+        let power = m.power? as f32;
+        let (attack, defense) = m.category.stats();
+        let attack = self.stat(attack) as f32;
+        let defense = target.stat(defense) as f32;
+        let base = ((2.0 * self.level as f32 / 5.0 + 2.0) * power * attack / defense) / 50.0 + 2.0;
+        Some((base * self.stab(m.pokemon_type) * effective.multiplier() * random) as Health)
+    }
+
+    /// The same type attack bonus of a move with this type. Moves that share a type with the user deal 1.5x damage.
+    pub fn stab(&self, move_type: PokemonType) -> f32 {
+        match self.pokemon.has_type(move_type) {
+            true => 1.5,
+            false => 1.0,
+        }
+    }
+
+    /// The critical hit stage of a move used by this pokemon.
+    /// Affection only adds to it if `affection` is true, as affection effects are not in every game.
+    pub fn crit_stage(&self, m: &Move, affection: bool) -> u8 {
+        let bonus = match affection {
+            true => self.affection_crit_bonus(),
+            false => 0,
+        };
+        (m.crit_stage() + bonus).min(3)
+    }
+
+    /// The average damage of a move once its accuracy is taken into account.
+    pub fn expected_damage(&self, move_index: usize, target: &Self, random_avg: f32) -> Option<Health> {
+        self.expected_damage_in(None, move_index, target, random_avg)
+    }
+
+    /// The average damage of a move using a custom [TypeChart].
+    pub fn expected_damage_with(
+        &self,
+        chart: &TypeChart,
+        move_index: usize,
+        target: &Self,
+        random_avg: f32,
+    ) -> Option<Health> {
+        self.expected_damage_in(Some(chart), move_index, target, random_avg)
+    }
+
+    fn expected_damage_in(
+        &self,
+        chart: Option<&TypeChart>,
+        move_index: usize,
+        target: &Self,
+        random_avg: f32,
+    ) -> Option<Health> {
+        let m = &self.moves.get(move_index)?.m;
+        let damage = self.damage_effective(m, target, Self::effective(chart, m, target), random_avg)?;
+        let chance = m.accuracy.map(|accuracy| accuracy as f32 / 100.0).unwrap_or(1.0);
+        Some((damage as f32 * chance) as Health)
+    }
+
+    /// The index of the usable move with the highest expected damage against the target,
+    /// preferring super effective moves. Returns [None] if no usable move deals damage.
+    pub fn recommended_move(&self, target: &Self, random_avg: f32) -> Option<usize> {
+        self.recommended_move_in(None, target, random_avg)
+    }
+
+    /// The recommended move against the target using a custom [TypeChart].
+    pub fn recommended_move_with(&self, chart: &TypeChart, target: &Self, random_avg: f32) -> Option<usize> {
+        self.recommended_move_in(Some(chart), target, random_avg)
     }
 
-    pub fn heal(&mut self, hp: Option<Health>, pp: Option<PP>) {
-        self.heal_hp(hp);
-        self.heal_pp(pp);
+    fn recommended_move_in(&self, chart: Option<&TypeChart>, target: &Self, random_avg: f32) -> Option<usize> {
+        self.moves
+            .iter()
+            .enumerate()
+            .filter(|(.., m)| !m.empty())
+            .filter_map(|(index, m)| {
+                let damage = self.expected_damage_in(chart, index, target, random_avg)?;
+                let super_effective = Self::effective(chart, &m.m, target).is_super_effective();
+                Some((index, (super_effective, damage)))
+            })
+            .max_by_key(|(.., key)| *key)
+            .map(|(index, ..)| index)
     }
 
-    pub fn heal_hp(&mut self, amount: Option<Health>) {
+    /// Heals the pokemon's HP and the PP of each of its moves. If no amount is given, it is fully restored.
+    /// Returns the amount of HP and the total PP restored.
+    pub fn heal(&mut self, hp: Option<Health>, pp: Option<PP>) -> (Health, PP) {
+        (self.heal_hp(hp), self.heal_pp(pp))
+    }
+
+    /// Heals the pokemon without going over its max HP. If no amount is given, HP is fully restored.
+    /// Returns the amount of HP restored.
+    pub fn heal_hp(&mut self, amount: Option<Health>) -> Health {
         let max = self.max_hp();
-        self.hp = amount.unwrap_or(max).min(max);
+        let healed = amount.unwrap_or(max).min(max.saturating_sub(self.hp));
+        self.hp += healed;
+        healed
+    }
+
+    fn fraction_hp(&self, fraction: f32) -> Health {
+        (self.max_hp() as f32 * fraction) as Health
+    }
+
+    /// Deals damage equal to a fraction of the pokemon's max HP. Returns the HP lost.
+    pub fn damage_fraction(&mut self, fraction: f32) -> Health {
+        let damage = self.fraction_hp(fraction).min(self.hp);
+        self.hp -= damage;
+        damage
+    }
+
+    /// Heals a fraction of the pokemon's max HP. Returns the HP restored.
+    pub fn heal_fraction(&mut self, fraction: f32) -> Health {
+        let heal = self.fraction_hp(fraction).min(self.max_hp().saturating_sub(self.hp));
+        self.hp += heal;
+        heal
     }
 
-    pub fn heal_pp(&mut self, amount: Option<PP>) {
-        self.moves.iter_mut().for_each(|i| i.restore(amount))
+    /// Sets the HP of both pokemon to the average of their HP.
+    pub fn pain_split(&mut self, other: &mut Self) {
+        let average = ((self.hp as u32 + other.hp as u32) / 2) as Health;
+        self.hp = average.min(self.max_hp());
+        other.hp = average.min(other.max_hp());
+    }
+
+    /// Adds PP to each of the pokemon's moves without going over their max PP. If no amount is given, PP is fully restored.
+    /// Returns the total PP restored.
+    pub fn heal_pp(&mut self, amount: Option<PP>) -> PP {
+        self.moves.iter_mut().map(|m| m.add_pp(amount)).sum()
     }
 
     pub fn fainted(&self) -> bool {
@@ -173,33 +581,284 @@ impl<'a> OwnedRefPokemon<'a> {
         self.pokemon.moves_at_level(self.level)
     }
 
-    pub fn add_exp(&mut self, experience: Experience) -> impl Iterator<Item = MoveId> + '_ {
-        // add exp to pokemon
+    /// Teaches the pokemon the four strongest moves in its learnset, replacing weaker ones.
+    /// Moves are ranked by their [OwnedPokemon::expected_damage_with] against a copy of the pokemon
+    /// of each type, added together. New moves take the place of the moves they replace.
+    pub fn learn_best_moves(&mut self, movedex: &'a Movedex, chart: &TypeChart) -> LearnedMoves {
+        let species = PokemonType::ALL
+            .iter()
+            .filter(|pokemon_type| **pokemon_type != PokemonType::Unknown)
+            .map(|pokemon_type| Pokemon {
+                primary_type: *pokemon_type,
+                secondary_type: None,
+                ..self.pokemon.value().clone()
+            })
+            .collect::<Vec<_>>();
+        let targets = species
+            .iter()
+            .map(|species| self.with_species(IdRef::of(species), Default::default()))
+            .collect::<Vec<_>>();
 
-        self.experience += experience * 5;
+        let score = |m: MoveRef<'a>| {
+            let user = self.with_species(self.pokemon, [OwnedRefMove::new(m)].iter().copied().collect());
+            targets
+                .iter()
+                .flat_map(|target| user.expected_damage_with(chart, 0, target, 1.0))
+                .map(|damage| damage as u32)
+                .sum::<u32>()
+        };
 
-        // level the pokemon up if they reach a certain amount of exp (and then subtract the exp by the maximum for the previous level)
+        let mut ranked = self.moves.iter().map(|m| m.m).collect::<Vec<_>>();
+        for learnable in self.pokemon.moves.iter() {
+            if !ranked.iter().any(|m| m.id == learnable.1) {
+                if let Some(m) = movedex.try_get(&learnable.1) {
+                    ranked.push(m);
+                }
+            }
+        }
+        let mut ranked = ranked.into_iter().map(|m| (m, score(m))).collect::<Vec<_>>();
+        // the sort is stable, so moves the pokemon already knows win ties
+        ranked.sort_by(|(_, a), (_, b)| b.cmp(a));
+        let best = ranked
+            .into_iter()
+            .take(MOVESET_LENGTH)
+            .map(|(m, _)| m)
+            .collect::<Vec<_>>();
 
+        let mut new = best
+            .iter()
+            .filter(|m| !self.moves.iter().any(|known| known.m.id == m.id))
+            .copied()
+            .collect::<Vec<_>>()
+            .into_iter();
+        let mut learned = LearnedMoves::default();
+        for known in self.moves.iter_mut() {
+            if !best.iter().any(|m| m.id == known.m.id) {
+                if let Some(m) = new.next() {
+                    learned.replaced.push(known.m.id);
+                    learned.learned.push(m.id);
+                    *known = OwnedRefMove::new(m);
+                }
+            }
+        }
+        for m in new {
+            learned.learned.push(m.id);
+            self.moves.push(OwnedRefMove::new(m));
+        }
+        learned
+    }
+
+    /// A copy of the pokemon as another species with other moves, which can borrow from a shorter lived dex.
+    fn with_species<'s>(&self, pokemon: PokemonRef<'s>, moves: MoveSet<OwnedRefMove<'s>>) -> OwnedRefPokemon<'s>
+    where
+        'a: 's,
+    {
+        OwnedPokemon {
+            pokemon,
+            nickname: self.nickname.clone(),
+            level: self.level,
+            gender: self.gender,
+            nature: self.nature,
+            shiny: self.shiny,
+            ivs: self.ivs,
+            evs: self.evs,
+            experience: self.experience,
+            friendship: self.friendship,
+            affection: self.affection,
+            pokerus: self.pokerus,
+            scale: self.scale,
+            stat_snapshot: self.stat_snapshot,
+            stat_formula: self.stat_formula,
+            moves: MoveRefSet::new(self.moves.movedex, moves),
+            ailment: self.ailment,
+            item: self.item,
+            consumed_item: self.consumed_item,
+            hp: self.hp,
+        }
+    }
+
+    /// Every move the pokemon could have learned by leveling up to its level that it does not know, without duplicates.
+    /// Moves chosen from here can be learned with [OwnedPokemon::relearn].
+    pub fn relearnable_moves(&self) -> impl Iterator<Item = &MoveId> + '_ {
+        let level = self.level;
+        let known: MoveSet<MoveId> = self.moves.iter().map(|m| m.m.id).collect();
+        let moves = &self.pokemon.moves;
+        moves
+            .iter()
+            .enumerate()
+            .filter(move |(.., learnable)| learnable.0 <= level)
+            .filter(move |(index, learnable)| {
+                !moves[..*index]
+                    .iter()
+                    .any(|previous| previous.0 <= level && previous.1 == learnable.1)
+            })
+            .map(|(.., learnable)| &learnable.1)
+            .filter(move |id| !known.contains(id))
+    }
+
+    /// Teaches the pokemon a move it could have learned by leveling up.
+    /// If the pokemon knows a full set of moves, the move at `index` is replaced.
+    /// If a cost is given, the item is taken from the bag.
+    pub fn relearn(
+        &mut self,
+        id: &MoveId,
+        index: usize,
+        cost: Option<(&mut Bag<'_>, &ItemId, StackSize)>,
+    ) -> Result<(), RelearnError> {
+        if !self
+            .pokemon
+            .moves
+            .iter()
+            .any(|learnable| learnable.0 <= self.level && &learnable.1 == id)
+        {
+            return Err(RelearnError::NotLearnable);
+        }
+        if self.moves.iter().any(|m| &m.m.id == id) {
+            return Err(RelearnError::AlreadyKnown);
+        }
+        let m = self
+            .moves
+            .movedex
+            .try_get(id)
+            .ok_or(RelearnError::NotLearnable)?;
+        if self.moves.is_full() && index >= self.moves.len() {
+            return Err(RelearnError::InvalidIndex);
+        }
+        if let Some((bag, item, count)) = cost {
+            if !bag.take_item(item, count) {
+                return Err(RelearnError::MissingItem);
+            }
+        }
+        match self.moves.is_full() {
+            true => self.moves[index] = OwnedRefMove::new(m),
+            false => self.moves.push(OwnedRefMove::new(m)),
+        }
+        Ok(())
+    }
+
+    pub fn add_exp(&mut self, experience: Experience) -> impl Iterator<Item = MoveId> + '_ {
         let gr = self.pokemon.training.growth_rate;
 
+        let max_level = *LEVEL_RANGE.end();
+
         let previous = self.level;
 
-        while self.experience > gr.max_exp(self.level) {
-            self.experience -= gr.max_exp(self.level);
+        // add exp to pokemon, unless it is already at the maximum level
+
+        if self.level < max_level {
+            self.experience = self
+                .experience
+                .saturating_add(experience.saturating_mul(5));
+        }
+
+        // level the pokemon up if they reach a certain amount of exp (and then subtract the exp by the amount needed for the previous level)
+
+        while self.level < max_level && self.experience >= gr.level_exp(self.level) {
+            self.experience -= gr.level_exp(self.level);
             self.level += 1;
         }
 
+        if self.level == max_level {
+            self.experience = 0;
+        }
+
         self.on_level_up(previous)
     }
 
+    fn evolution(&self, trigger: EvolutionTrigger) -> Option<&'a Evolution> {
+        let held = self.item.as_ref().map(|item| &item.id);
+        self.pokemon
+            .value()
+            .evolutions
+            .iter()
+            .find(|evolution| evolution.matches(&trigger, self.level, self.friendship, held))
+    }
+
+    /// Returns the pokemon this pokemon should evolve into after the trigger, if any.
+    /// If more than one evolution matches, the first one is chosen.
+    pub fn should_evolve(&self, trigger: EvolutionTrigger) -> Option<PokemonId> {
+        self.evolution(trigger).map(|evolution| evolution.into)
+    }
+
+    /// Returns the pokemon this pokemon should evolve into after being traded, if any.
+    pub fn should_evolve_trade(&self) -> Option<PokemonId> {
+        self.should_evolve(EvolutionTrigger::Trade)
+    }
+
+    /// Evolves the pokemon if it should evolve after being traded, returning the pokemon it evolved into.
+    /// If the evolution needs a held item, the item is used up once the pokemon has evolved.
+    pub fn trade_evolve(&mut self, pokedex: &'a Pokedex) -> Option<PokemonId> {
+        let evolution = self.evolution(EvolutionTrigger::Trade)?;
+        if !self.evolve(pokedex, &evolution.into) {
+            return None;
+        }
+        if let EvolutionType::Trade(Some(..)) = evolution.kind {
+            self.item = None;
+        }
+        Some(evolution.into)
+    }
+
+    /// Evolves the pokemon into another species, keeping its HP proportional to its new max HP.
+    /// A pokemon that has not fainted keeps at least 1 HP.
+    /// Moves the new species learns at the pokemon's level are added if there is space for them.
+    /// Returns false and leaves the pokemon unchanged if the target is not in the pokedex.
+    pub fn evolve(&mut self, pokedex: &'a Pokedex, target: &PokemonId) -> bool {
+        let pokemon = match pokedex.try_get(target) {
+            Some(pokemon) => pokemon,
+            None => return false,
+        };
+        let percent = self.percent_hp();
+        self.pokemon = pokemon;
+        // a snapshot holds the old species' stats
+        self.stat_snapshot = None;
+        let hp = (self.max_hp() as f32 * percent).round() as Health;
+        self.hp = match self.hp {
+            0 => 0,
+            _ => hp.max(1),
+        };
+        for id in pokemon.value().moves_at_level(self.level) {
+            if self.moves.is_full() {
+                break;
+            }
+            if !self.moves.iter().any(|m| m.m.id == id) {
+                if let Some(m) = self.moves.movedex.try_get(&id) {
+                    self.moves.push(OwnedRefMove::new(m));
+                }
+            }
+        }
+        true
+    }
+
+    /// The experience this pokemon needs to reach its next level, or 0 at the maximum level.
+    pub fn exp_to_next_level(&self) -> Experience {
+        self.pokemon
+            .training
+            .growth_rate
+            .level_exp(self.level)
+            .saturating_sub(self.experience)
+    }
+
+    /// The total experience this pokemon has gained across all of its levels.
+    pub fn total_exp(&self) -> Experience {
+        self.pokemon
+            .training
+            .growth_rate
+            .max_exp(self.level)
+            .saturating_add(self.experience)
+    }
+
     pub fn exp_from(&self) -> Experience {
         self.pokemon.exp_from(self.level)
     }
 
     pub fn on_level_up(&mut self, previous: Level) -> impl Iterator<Item = MoveId> + '_ {
+        for _ in previous..self.level {
+            self.level_up_friendship();
+        }
+
         // Get the moves the pokemon learns at the level it just gained.
 
-        let mut moves = self.pokemon.moves_at(previous..self.level).into_iter();
+        let mut moves = self.pokemon.moves_at(previous..self.level);
 
         // Add moves if the player's pokemon does not have a full set of moves.
 
@@ -218,35 +877,63 @@ impl<'a> OwnedRefPokemon<'a> {
     }
 
     pub fn try_use_item(&mut self, item: &Item) -> bool {
-        if !item.usage.conditions.iter().any(|c| match c {
+        !self.use_item(item, None).is_empty()
+    }
+
+    /// Applies the actions of an item to the pokemon, returning what happened.
+    /// `move_index` chooses which move has its PP restored. If it is [None], every move is restored.
+    /// If the item has no effect, nothing is returned.
+    pub fn use_item(&mut self, item: &Item, move_index: Option<usize>) -> Vec<ItemOutcome> {
+        if !item.usage.conditions.iter().all(|c| match c {
             ItemCondition::Fainted => self.fainted(),
         }) {
-            return false;
+            return Vec::new();
         }
+        // only items made for fainted pokemon, like revives, can heal them, but other effects such as restoring PP still apply
+        let revives = item
+            .usage
+            .conditions
+            .iter()
+            .any(|c| matches!(c, ItemCondition::Fainted));
+        let fainted = self.fainted();
         match &item.usage.kind {
-            ItemUsageKind::Actions(actions) => {
-                for action in actions {
-                    match action {
-                        ItemAction::CurePokemon(status) => {
-                            if let Some(effect) = &self.ailment {
-                                if let Some(status) = status {
-                                    if &effect.ailment == status {
-                                        self.ailment = None;
-                                    }
-                                } else {
-                                    self.ailment = None;
-                                }
-                            }
-                        }
-                        ItemAction::HealPokemon(hp) => {
-                            self.heal_hp(Some(*hp));
-                        }
-                    }
+            ItemUsageKind::Actions(actions) => actions
+                .iter()
+                .filter(|action| {
+                    revives || !fainted || !matches!(action, ItemAction::HealPokemon(..))
+                })
+                .flat_map(|action| self.apply_item_action(action, move_index))
+                .collect(),
+            ItemUsageKind::Script | ItemUsageKind::Pokeball | ItemUsageKind::None => Vec::new(),
+        }
+    }
+
+    fn apply_item_action(&mut self, action: &ItemAction, move_index: Option<usize>) -> Vec<ItemOutcome> {
+        match action {
+            ItemAction::CurePokemon(ailment) => {
+                let cured = self.ailment.map(|live| live.ailment);
+                match self.cure_ailment(ailment.as_ref()) {
+                    true => cured.map(ItemOutcome::Cured).into_iter().collect(),
+                    false => Vec::new(),
+                }
+            }
+            ItemAction::HealPokemon(hp) => {
+                match self.heal_hp(Some(*hp)) {
+                    0 => Vec::new(),
+                    healed => vec![ItemOutcome::Healed(healed)],
                 }
             }
-            ItemUsageKind::Script | ItemUsageKind::Pokeball | ItemUsageKind::None => return false,
+            ItemAction::RestorePP(pp) => self
+                .moves
+                .iter_mut()
+                .enumerate()
+                .filter(|(index, ..)| move_index.map(|i| &i == index).unwrap_or(true))
+                .filter_map(|(index, m)| match m.add_pp(*pp) {
+                    0 => None,
+                    restored => Some(ItemOutcome::RestoredPP(index, restored)),
+                })
+                .collect(),
         }
-        true
     }
 
     pub fn uninit(self) -> OwnedIdPokemon {
@@ -255,25 +942,70 @@ impl<'a> OwnedRefPokemon<'a> {
             level: self.level,
             nickname: self.nickname,
             gender: self.gender,
+            nature: self.nature,
+            shiny: self.shiny,
             moves: self.moves.set.into_iter().map(OwnedRefMove::uninit).collect(),
             hp: Some(self.hp),
             item: self.item.map(|item| item.id),
+            consumed_item: self.consumed_item.map(|item| item.id),
             ailment: self.ailment,
             ivs: self.ivs,
             evs: self.evs,
             experience: self.experience,
             friendship: self.friendship,
+            affection: self.affection,
+            pokerus: self.pokerus,
+            scale: self.scale,
+            stat_snapshot: None,
+            stat_formula: self.stat_formula,
         }
     }
 
+    /// Throws the held item if it can be flung, returning it along with its fling power.
+    /// The item is remembered as the pokemon's consumed item.
+    pub fn fling(&mut self) -> Option<(ItemRef<'a>, Power)> {
+        let power = self.item.as_ref()?.fling_power?;
+        let item = self.item.take()?;
+        self.consumed_item = Some(item);
+        Some((item, power))
+    }
+
+    /// Uses the held item on the pokemon, remembering it as the pokemon's consumed item.
+    /// The pokemon keeps holding the item if it could not be used.
     pub fn use_held_item(&mut self) -> bool {
-        match self.item.take() {
-            Some(item) => self.try_use_item(&item),
-            None => false,
+        let item = match self.item.take() {
+            Some(item) => item,
+            None => return false,
+        };
+        let used = self.try_use_item(&item);
+        match used {
+            true => self.consumed_item = Some(item),
+            false => self.item = Some(item),
         }
+        used
     }
 }
 
+/// The moves changed by [OwnedPokemon::learn_best_moves].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LearnedMoves {
+    /// The moves the pokemon learned.
+    pub learned: Vec<MoveId>,
+    /// The moves the pokemon forgot. Each one was replaced by the move at the same index in [LearnedMoves::learned].
+    pub replaced: Vec<MoveId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelearnError {
+    /// The pokemon cannot learn the move by leveling up at its current level.
+    NotLearnable,
+    AlreadyKnown,
+    /// The bag does not have enough of the item needed to relearn the move.
+    MissingItem,
+    /// The index of the move to replace is out of range.
+    InvalidIndex,
+}
+
 impl Display for OwnedIdPokemon {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         write!(f, "ID {}, Lv. {}", self.pokemon, self.level)
@@ -285,3 +1017,1113 @@ impl<'a> Display for OwnedRefPokemon<'a> {
         write!(f, "Lv. {} {}", self.level, self.pokemon.name)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ailment::{Ailment, LiveAilment},
+        item::{bag::Bag, usage::ItemOutcome, ItemStack},
+        moves::Move,
+        pokemon::{
+            stat::{Stat, StatFormula, StatSnapshot, StatType, Stats},
+            Evolution, EvolutionTrigger, EvolutionType, Experience, Friendship, Gender, GrowthRate, Health, LearnableMove, LearnedMoves, Nature, OwnedIdPokemon, Pokemon, PokemonId, Pokerus,
+            RelearnError, SizeMark, Training,
+        },
+        testing::{id, itemdex, stats, Dexes, TestRng, BULBASAUR, CHARMANDER, GASTLY, GEODUDE, GRAVELER, PIDGEY, SKARMORY},
+        types::{Effective, PokemonType, TypeChart},
+    };
+
+    #[test]
+    fn checksum_covers_saved_fields() {
+        let saved = Dexes::saved(PIDGEY, 5);
+        assert_eq!(saved.checksum(), Dexes::saved(PIDGEY, 5).checksum());
+
+        let mut nicknamed = saved.clone();
+        nicknamed.nickname = Some("Pidge".to_owned());
+        assert_ne!(saved.checksum(), nicknamed.checksum());
+
+        // fields that are not saved do not change the checksum
+        let mut formula = saved.clone();
+        formula.stat_formula = StatFormula::Gen1;
+        assert_eq!(saved.checksum(), formula.checksum());
+    }
+
+    #[test]
+    fn stat_formula_is_used() {
+        let dexes = Dexes::new();
+        let mut saved = Dexes::saved(PIDGEY, 50);
+        saved.nature = Nature::Adamant;
+
+        let gen3 = dexes.init(saved.clone());
+        saved.stat_formula = StatFormula::Gen1;
+        let gen1 = dexes.init(saved);
+
+        let pidgey = gen1.pokemon.value();
+        let (ivs, evs) = (&gen1.ivs, &gen1.evs);
+        assert_eq!(
+            gen1.stat(StatType::Attack),
+            StatFormula::Gen1.stat(pidgey.base.atk, ivs.atk, evs.atk, 50)
+        );
+        assert_eq!(gen1.max_hp(), StatFormula::Gen1.hp(pidgey.base.hp, ivs.hp, evs.hp, 50));
+        assert_eq!(gen1.hp, gen1.max_hp());
+        // Adamant raises attack in gen 3 but gen 1 has no natures
+        assert_eq!(
+            gen3.stat(StatType::Attack),
+            Nature::Adamant.apply(
+                StatType::Attack,
+                StatFormula::Gen3.stat(pidgey.base.atk, ivs.atk, evs.atk, 50)
+            )
+        );
+        assert!(gen3.stat(StatType::Attack) > StatFormula::Gen3.stat(pidgey.base.atk, ivs.atk, evs.atk, 50));
+    }
+
+    #[test]
+    fn fling_throws_held_item() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 10);
+        pokemon.item = dexes.itemdex.try_get(&id("kings_rock"));
+
+        let (item, power) = pokemon.fling().unwrap();
+        assert_eq!(item.id, id("kings_rock"));
+        assert_eq!(power, 30);
+        assert!(pokemon.item.is_none());
+        assert!(pokemon.fling().is_none());
+    }
+
+    #[test]
+    fn fling_needs_fling_power() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 10);
+        pokemon.item = dexes.itemdex.try_get(&id("potion"));
+
+        assert!(pokemon.fling().is_none());
+        assert!(pokemon.item.is_some());
+    }
+
+    #[test]
+    fn recycle_restores_consumed_item() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 10);
+        assert!(!pokemon.recycle());
+
+        pokemon.item = dexes.itemdex.try_get(&id("kings_rock"));
+        pokemon.fling();
+        assert!(pokemon.recycle());
+        assert_eq!(pokemon.item.unwrap().id, id("kings_rock"));
+        assert!(pokemon.consumed_item.is_none());
+        assert!(!pokemon.recycle());
+    }
+
+    #[test]
+    fn used_held_item_can_be_recycled() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 10);
+        pokemon.hp = 1;
+        pokemon.item = dexes.itemdex.try_get(&id("potion"));
+
+        assert!(pokemon.use_held_item());
+        assert_eq!(pokemon.hp, 21);
+        assert!(pokemon.item.is_none());
+        assert_eq!(pokemon.consumed_item.unwrap().id, id("potion"));
+    }
+
+    #[test]
+    fn unusable_held_item_is_kept() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 10);
+        pokemon.item = dexes.itemdex.try_get(&id("potion"));
+
+        assert!(!pokemon.use_held_item());
+        assert!(pokemon.item.is_some());
+        assert!(pokemon.consumed_item.is_none());
+    }
+
+    #[test]
+    fn potion_heals_damaged_pokemon() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 10);
+        let potion = dexes.itemdex.get(&id("potion"));
+
+        assert!(pokemon.use_item(&potion, None).is_empty());
+        pokemon.hp = 1;
+        assert_eq!(pokemon.use_item(&potion, None), vec![ItemOutcome::Healed(20)]);
+        assert_eq!(pokemon.hp, 21);
+    }
+
+    #[test]
+    fn fainted_pokemon_need_a_revive() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 10);
+        pokemon.hp = 0;
+
+        let potion = dexes.itemdex.get(&id("potion"));
+        assert!(pokemon.use_item(&potion, None).is_empty());
+        assert!(pokemon.fainted());
+
+        let revive = dexes.itemdex.get(&id("revive"));
+        let max_hp = pokemon.max_hp();
+        assert_eq!(pokemon.use_item(&revive, None), vec![ItemOutcome::Healed(max_hp)]);
+        assert!(!pokemon.fainted());
+        // every condition has to hold, so a revive does nothing on a pokemon that has not fainted
+        pokemon.hp = 1;
+        assert!(pokemon.use_item(&revive, None).is_empty());
+    }
+
+    #[test]
+    fn ether_works_on_fainted_pokemon() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 10);
+        let ether = dexes.itemdex.get(&id("ether"));
+        pokemon.hp = 0;
+        pokemon.moves[0].pp = 0;
+
+        assert_eq!(pokemon.use_item(&ether, Some(0)), vec![ItemOutcome::RestoredPP(0, 10)]);
+        assert_eq!(pokemon.moves[0].pp, 10);
+        assert!(pokemon.fainted());
+    }
+
+    #[test]
+    fn ether_restores_chosen_move() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 10);
+        let ether = dexes.itemdex.get(&id("ether"));
+        for m in pokemon.moves.iter_mut() {
+            m.pp = 0;
+        }
+
+        assert_eq!(pokemon.use_item(&ether, Some(1)), vec![ItemOutcome::RestoredPP(1, 10)]);
+        assert_eq!(pokemon.moves[0].pp, 0);
+        assert_eq!(
+            pokemon.use_item(&ether, None),
+            vec![ItemOutcome::RestoredPP(0, 10), ItemOutcome::RestoredPP(1, 10)]
+        );
+    }
+
+    #[test]
+    fn relearn_takes_the_cost_from_a_bag() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(BULBASAUR, 13);
+        assert_eq!(pokemon.relearnable_moves().collect::<Vec<_>>(), vec![&id("tackle")]);
+
+        // the bag does not need to borrow the same dex as the pokemon
+        let itemdex = itemdex();
+        let mut bag = Bag::init(
+            &itemdex,
+            vec![ItemStack {
+                item: id("potion"),
+                count: 1,
+            }],
+        );
+        let potion = id("potion");
+
+        assert_eq!(
+            pokemon.relearn(&id("earthquake"), 0, Some((&mut bag, &potion, 1))),
+            Err(RelearnError::NotLearnable)
+        );
+        assert_eq!(
+            pokemon.relearn(&id("vine_whip"), 0, Some((&mut bag, &potion, 1))),
+            Err(RelearnError::AlreadyKnown)
+        );
+        assert_eq!(
+            pokemon.relearn(&id("tackle"), 4, Some((&mut bag, &potion, 1))),
+            Err(RelearnError::InvalidIndex)
+        );
+        assert_eq!(
+            pokemon.relearn(&id("tackle"), 0, Some((&mut bag, &potion, 2))),
+            Err(RelearnError::MissingItem)
+        );
+        assert_eq!(bag.count(&potion), 1);
+
+        assert_eq!(pokemon.relearn(&id("tackle"), 0, Some((&mut bag, &potion, 1))), Ok(()));
+        assert_eq!(pokemon.moves[0].m.id, id("tackle"));
+        assert_eq!(bag.count(&potion), 0);
+    }
+
+    #[test]
+    fn relearn_without_a_cost() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(BULBASAUR, 3);
+        pokemon.moves.clear();
+
+        assert_eq!(pokemon.relearn(&id("growl"), 0, None), Ok(()));
+        assert_eq!(pokemon.moves.len(), 1);
+        assert_eq!(pokemon.relearnable_moves().collect::<Vec<_>>(), vec![&id("tackle")]);
+    }
+
+    #[test]
+    fn affection_crit_bonus_is_opt_in() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 10);
+        let tackle = dexes.movedex.get(&id("tackle"));
+
+        pokemon.affection = 249;
+        assert_eq!(pokemon.affection_hearts(), 4);
+        assert_eq!(pokemon.crit_stage(&tackle, true), 0);
+
+        pokemon.affection = 255;
+        assert_eq!(pokemon.affection_hearts(), 5);
+        assert_eq!(pokemon.affection_crit_bonus(), 1);
+        assert_eq!(pokemon.crit_stage(&tackle, true), 1);
+        assert_eq!(pokemon.crit_stage(&tackle, false), 0);
+
+        let high_crit = Move {
+            crit_rate: 3,
+            ..tackle.value().clone()
+        };
+        assert_eq!(pokemon.crit_stage(&high_crit, true), 3);
+    }
+
+    #[test]
+    fn affection_endure_needs_three_hearts() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 10);
+        let mut random = TestRng::new(7);
+
+        pokemon.affection = 149;
+        assert!((0..1000).all(|_| !pokemon.affection_endure(&mut random)));
+
+        pokemon.affection = 255;
+        let endured = (0..1000).filter(|_| pokemon.affection_endure(&mut random)).count();
+        assert!((100..300).contains(&endured), "{}", endured);
+    }
+
+    #[test]
+    fn heal_reports_the_amount_restored() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 10);
+        let max_hp = pokemon.max_hp();
+
+        pokemon.hp = max_hp - 10;
+        assert_eq!(pokemon.heal_hp(Some(50)), 10);
+        assert_eq!(pokemon.hp, max_hp);
+        assert_eq!(pokemon.heal_hp(None), 0);
+    }
+
+    #[test]
+    fn heal_adds_pp() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 10);
+        let max_hp = pokemon.max_hp();
+        pokemon.hp = 1;
+        // tackle and gust both have 35 PP
+        pokemon.moves[0].pp = 30;
+        pokemon.moves[1].pp = 0;
+
+        assert_eq!(pokemon.heal(Some(5), Some(10)), (5, 15));
+        assert_eq!(pokemon.hp, 6);
+        assert_eq!(pokemon.moves[0].pp, 35);
+        assert_eq!(pokemon.moves[1].pp, 10);
+
+        assert_eq!(pokemon.heal(None, None), (max_hp - 6, 25));
+        assert_eq!(pokemon.hp, max_hp);
+        assert_eq!(pokemon.moves[1].pp, 35);
+    }
+
+    fn trade_evolves(into: PokemonId, item: Option<&str>) -> Dexes {
+        let mut dexes = Dexes::new();
+        dexes.pokedex.inner_mut().get_mut(&GEODUDE).unwrap().evolutions = vec![Evolution {
+            into,
+            kind: EvolutionType::Trade(item.map(id)),
+        }];
+        dexes
+    }
+
+    #[test]
+    fn trade_evolution_needs_the_held_item() {
+        let dexes = trade_evolves(GRAVELER, Some("kings_rock"));
+        let mut pokemon = dexes.owned(GEODUDE, 30);
+
+        assert_eq!(pokemon.should_evolve_trade(), None);
+        assert_eq!(pokemon.trade_evolve(&dexes.pokedex), None);
+        assert_eq!(pokemon.pokemon.id, GEODUDE);
+
+        pokemon.item = dexes.itemdex.try_get(&id("kings_rock"));
+        assert_eq!(pokemon.should_evolve_trade(), Some(GRAVELER));
+        // checking does not use up the item
+        assert!(pokemon.item.is_some());
+
+        assert_eq!(pokemon.trade_evolve(&dexes.pokedex), Some(GRAVELER));
+        assert_eq!(pokemon.pokemon.id, GRAVELER);
+        assert!(pokemon.item.is_none());
+    }
+
+    #[test]
+    fn trade_evolution_without_an_item() {
+        let dexes = trade_evolves(GRAVELER, None);
+        let mut pokemon = dexes.owned(GEODUDE, 30);
+        pokemon.item = dexes.itemdex.try_get(&id("potion"));
+
+        assert_eq!(pokemon.trade_evolve(&dexes.pokedex), Some(GRAVELER));
+        // items that are not part of the evolution are kept
+        assert_eq!(pokemon.item.unwrap().id, id("potion"));
+    }
+
+    #[test]
+    fn failed_trade_evolution_keeps_the_item() {
+        let dexes = trade_evolves(76, Some("kings_rock"));
+        let mut pokemon = dexes.owned(GEODUDE, 30);
+        pokemon.item = dexes.itemdex.try_get(&id("kings_rock"));
+
+        assert_eq!(pokemon.trade_evolve(&dexes.pokedex), None);
+        assert_eq!(pokemon.pokemon.id, GEODUDE);
+        assert!(pokemon.item.is_some());
+    }
+
+    #[test]
+    fn evolve_keeps_hp_proportional() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(GEODUDE, 30);
+        pokemon.nickname = Some("Rocky".to_owned());
+        pokemon.hp = pokemon.max_hp() / 2;
+        let percent = pokemon.percent_hp();
+
+        assert!(pokemon.evolve(&dexes.pokedex, &GRAVELER));
+        assert_eq!(pokemon.pokemon.id, GRAVELER);
+        assert_eq!(pokemon.hp, (pokemon.max_hp() as f32 * percent).round() as Health);
+        assert_eq!(pokemon.name(), "Rocky");
+
+        pokemon.nickname = None;
+        assert_eq!(pokemon.name(), "Graveler");
+    }
+
+    #[test]
+    fn evolve_clears_the_stat_snapshot() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(GEODUDE, 30);
+        let mut snapshot = pokemon.snapshot_stats();
+        snapshot.hp = 1000;
+        pokemon.apply_stat_snapshot(snapshot);
+        pokemon.hp = 1;
+
+        assert!(pokemon.evolve(&dexes.pokedex, &GRAVELER));
+        assert!(pokemon.stat_snapshot.is_none());
+        assert!(pokemon.max_hp() < 1000);
+        // 1/1000 of the new max HP rounds down to 0, but the pokemon has not fainted
+        assert_eq!(pokemon.hp, 1);
+    }
+
+    #[test]
+    fn evolve_keeps_fainted_pokemon_fainted() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(GEODUDE, 30);
+        pokemon.hp = 0;
+
+        assert!(pokemon.evolve(&dexes.pokedex, &GRAVELER));
+        assert!(pokemon.fainted());
+    }
+
+    #[test]
+    fn evolve_into_missing_pokemon() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(GEODUDE, 30);
+        let hp = pokemon.hp;
+
+        assert!(!pokemon.evolve(&dexes.pokedex, &76));
+        assert_eq!(pokemon.pokemon.id, GEODUDE);
+        assert_eq!(pokemon.hp, hp);
+    }
+
+    #[test]
+    fn damage_helpers_use_the_given_chart() {
+        let dexes = Dexes::new();
+        let pidgey = dexes.owned(PIDGEY, 10);
+        let gastly = dexes.owned(GASTLY, 10);
+        let mut chart = TypeChart::default();
+        chart.set(PokemonType::Normal, PokemonType::Ghost, Effective::Effective);
+
+        let tackle = pidgey.moves.iter().position(|m| m.m.id == id("tackle")).unwrap();
+        let gust = pidgey.moves.iter().position(|m| m.m.id == id("gust")).unwrap();
+
+        // tackle does nothing to ghosts with the standard chart, so gust is the only choice
+        assert_eq!(pidgey.expected_damage(tackle, &gastly, 1.0), Some(0));
+        assert_eq!(pidgey.recommended_move(&gastly, 1.0), Some(gust));
+
+        // both moves get STAB, but Pidgey's attack against Gastly's defense is higher than the special stats
+        let damage = pidgey.expected_damage_with(&chart, tackle, &gastly, 1.0).unwrap();
+        assert!(damage > 0);
+        assert_eq!(
+            Some(damage),
+            pidgey.damage_with(&chart, &pidgey.moves[tackle].m, &gastly, 1.0)
+        );
+        assert_eq!(pidgey.recommended_move_with(&chart, &gastly, 1.0), Some(tackle));
+        assert_eq!(
+            pidgey.expected_damage_with(&TypeChart::default(), gust, &gastly, 1.0),
+            pidgey.expected_damage(gust, &gastly, 1.0)
+        );
+    }
+
+    #[test]
+    fn learn_best_moves_replaces_weaker_moves() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(BULBASAUR, 13);
+        let known = pokemon.moves.iter().map(|m| m.m.id).collect::<Vec<_>>();
+        let growl = known.iter().position(|m| m == &id("growl")).unwrap();
+
+        let learned = pokemon.learn_best_moves(&dexes.movedex, &TypeChart::default());
+        assert_eq!(
+            learned,
+            LearnedMoves {
+                learned: vec![id("tackle")],
+                replaced: vec![id("growl")],
+            }
+        );
+        // tackle takes the place of growl and the other moves stay where they were
+        let moves = pokemon.moves.iter().map(|m| m.m.id).collect::<Vec<_>>();
+        for (index, m) in moves.iter().enumerate() {
+            match index == growl {
+                true => assert_eq!(m, &id("tackle")),
+                false => assert_eq!(m, &known[index]),
+            }
+        }
+
+        // the pokemon already knows its four strongest moves
+        assert_eq!(
+            pokemon.learn_best_moves(&dexes.movedex, &TypeChart::default()),
+            LearnedMoves::default()
+        );
+    }
+
+    #[test]
+    fn learn_best_moves_uses_the_chart() {
+        let mut dexes = Dexes::new();
+        dexes
+            .pokedex
+            .inner_mut()
+            .get_mut(&BULBASAUR)
+            .unwrap()
+            .moves
+            .push(LearnableMove(13, id("ember")));
+        let mut pokemon = dexes.owned(BULBASAUR, 13);
+        pokemon.moves.clear();
+        let mut chart = TypeChart::default();
+        for defender in PokemonType::ALL {
+            chart.set(PokemonType::Fire, defender, Effective::Ineffective);
+        }
+
+        let learned = pokemon.learn_best_moves(&dexes.movedex, &chart);
+        assert_eq!(
+            learned.learned,
+            vec![id("razor_leaf"), id("vine_whip"), id("absorb"), id("tackle")]
+        );
+
+        // with the standard chart ember is stronger than tackle, the weakest move
+        let learned = pokemon.learn_best_moves(&dexes.movedex, &TypeChart::default());
+        assert_eq!(
+            learned,
+            LearnedMoves {
+                learned: vec![id("ember")],
+                replaced: vec![id("tackle")],
+            }
+        );
+    }
+
+    #[test]
+    fn learn_best_moves_uses_the_full_learnset() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(BULBASAUR, 3);
+        assert_eq!(pokemon.moves.len(), 2);
+
+        // razor leaf, vine whip and absorb are learned above level 3, and growl deals no damage
+        let learned = pokemon.learn_best_moves(&dexes.movedex, &TypeChart::default());
+        assert_eq!(learned.replaced, vec![id("growl")]);
+        let mut moves = pokemon.moves.iter().map(|m| m.m.id).collect::<Vec<_>>();
+        moves.sort();
+        let mut strongest = vec![id("razor_leaf"), id("vine_whip"), id("tackle"), id("absorb")];
+        strongest.sort();
+        assert_eq!(moves, strongest);
+    }
+
+    #[test]
+    fn learn_best_moves_fills_empty_slots() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(CHARMANDER, 7);
+        pokemon.moves.clear();
+
+        let learned = pokemon.learn_best_moves(&dexes.movedex, &TypeChart::default());
+        assert!(learned.replaced.is_empty());
+        assert_eq!(learned.learned, vec![id("ember"), id("scratch"), id("growl")]);
+        assert_eq!(pokemon.moves.len(), 3);
+    }
+
+    fn poison() -> Option<LiveAilment> {
+        Some(LiveAilment {
+            ailment: Ailment::Poison,
+            turns: None,
+        })
+    }
+
+    #[test]
+    fn cure_ailment_matches_the_ailment() {
+        let mut saved = Dexes::saved(PIDGEY, 5);
+        assert!(!saved.cure_ailment(None));
+
+        saved.ailment = poison();
+        assert!(!saved.cure_ailment(Some(&Ailment::Paralysis)));
+        assert_eq!(saved.ailment, poison());
+        assert!(saved.cure_ailment(Some(&Ailment::Poison)));
+        assert!(saved.ailment.is_none());
+
+        saved.ailment = poison();
+        assert!(saved.cure_ailment(None));
+        assert!(saved.ailment.is_none());
+    }
+
+    #[test]
+    fn antidote_cures_poison_only() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 10);
+        let antidote = dexes.itemdex.get(&id("antidote"));
+        let full_heal = dexes.itemdex.get(&id("full_heal"));
+
+        pokemon.ailment = Some(LiveAilment {
+            ailment: Ailment::Paralysis,
+            turns: None,
+        });
+        assert!(pokemon.use_item(&antidote, None).is_empty());
+        assert!(pokemon.ailment.is_some());
+        assert_eq!(
+            pokemon.use_item(&full_heal, None),
+            vec![ItemOutcome::Cured(Ailment::Paralysis)]
+        );
+
+        pokemon.ailment = poison();
+        assert_eq!(pokemon.use_item(&antidote, None), vec![ItemOutcome::Cured(Ailment::Poison)]);
+        assert!(pokemon.ailment.is_none());
+    }
+
+    #[test]
+    fn expected_damage_accounts_for_accuracy() {
+        let dexes = Dexes::new();
+        let mut pidgey = dexes.owned(PIDGEY, 10);
+        let geodude = dexes.owned(GEODUDE, 10);
+        pidgey.replace_move(0, &id("mega_punch"));
+        pidgey.replace_move(1, &id("tackle"));
+
+        let strong = pidgey.damage(&pidgey.moves[0].m, &geodude, 1.0).unwrap();
+        let weak = pidgey.damage(&pidgey.moves[1].m, &geodude, 1.0).unwrap();
+        assert!(strong > weak);
+        // mega punch only hits half the time
+        assert_eq!(pidgey.expected_damage(0, &geodude, 1.0), Some(strong / 2));
+        assert!(pidgey.expected_damage(0, &geodude, 1.0) < pidgey.expected_damage(1, &geodude, 1.0));
+        assert_eq!(pidgey.expected_damage(2, &geodude, 1.0), None);
+
+        pidgey.replace_move(1, &id("growl"));
+        assert_eq!(pidgey.expected_damage(1, &geodude, 1.0), None);
+    }
+
+    #[test]
+    fn init_hp_is_clamped_to_max_hp() {
+        let dexes = Dexes::new();
+        for species in [BULBASAUR, CHARMANDER, PIDGEY, GEODUDE, SKARMORY] {
+            let pokemon = dexes.owned(species, 50);
+            assert_eq!(pokemon.hp, pokemon.max_hp());
+
+            let mut saved = Dexes::saved(species, 50);
+            saved.hp = Some(Health::MAX);
+            let pokemon = dexes.init(saved);
+            assert_eq!(pokemon.hp, pokemon.max_hp());
+        }
+
+        let mut saved = Dexes::saved(PIDGEY, 50);
+        saved.hp = Some(3);
+        assert_eq!(dexes.init(saved).hp, 3);
+    }
+
+    #[test]
+    fn pokerus_doubles_ev_gains() {
+        let defeated = Pokemon {
+            training: Training {
+                ev_yield: stats(0, 0, 1, 0, 0, 0),
+                ..Default::default()
+            },
+            ..Pokemon::unknown()
+        };
+        let mut saved = Dexes::saved(PIDGEY, 5);
+        assert_eq!(saved.gain_evs_from(&defeated), 1);
+
+        assert!(saved.infect(&mut TestRng::new(3)));
+        assert!(saved.pokerus.is_infected());
+        assert_eq!(saved.gain_evs_from(&defeated), 2);
+        assert_eq!(saved.evs.def, 3);
+    }
+
+    #[test]
+    fn pokerus_is_cured_after_its_days_run_out() {
+        let mut saved = Dexes::saved(PIDGEY, 5);
+        saved.pokerus = Pokerus::Infected { days_left: 2 };
+
+        saved.cure_tick();
+        assert_eq!(saved.pokerus, Pokerus::Infected { days_left: 1 });
+        saved.cure_tick();
+        assert_eq!(saved.pokerus, Pokerus::Cured);
+
+        // cured pokemon can not catch it again
+        assert!(!saved.infect(&mut TestRng::new(3)));
+        saved.cure_tick();
+        assert_eq!(saved.pokerus, Pokerus::Cured);
+    }
+
+    #[test]
+    fn stat_snapshot_overrides_stats() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 10);
+        let snapshot = pokemon.snapshot_stats();
+        assert_eq!(snapshot.atk, pokemon.stat(StatType::Attack));
+        assert_eq!(snapshot.hp, pokemon.max_hp());
+
+        let server = StatSnapshot {
+            hp: 10,
+            atk: 99,
+            ..snapshot
+        };
+        pokemon.apply_stat_snapshot(server);
+        assert_eq!(pokemon.stat(StatType::Attack), 99);
+        assert_eq!(pokemon.stat(StatType::Speed), snapshot.speed);
+        assert_eq!(pokemon.max_hp(), 10);
+        assert_eq!(pokemon.hp, 10);
+        assert_eq!(pokemon.snapshot_stats(), server);
+    }
+
+    fn with_level(level: u8) -> OwnedIdPokemon {
+        let mut json = serde_json::to_value(Dexes::saved(PIDGEY, 5)).unwrap();
+        json["level"] = level.into();
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn saved_levels_are_clamped() {
+        assert_eq!(with_level(0).level, 1);
+        assert_eq!(with_level(1).level, 1);
+        assert_eq!(with_level(42).level, 42);
+        assert_eq!(with_level(255).level, 100);
+    }
+
+    #[test]
+    fn fraction_damage_and_healing() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(GEODUDE, 50);
+        let max_hp = pokemon.max_hp();
+
+        // Super Fang on a pokemon at full HP
+        assert_eq!(pokemon.damage_fraction(0.5), max_hp / 2);
+        assert_eq!(pokemon.hp, max_hp - max_hp / 2);
+        assert_eq!(pokemon.damage_fraction(1.0), max_hp - max_hp / 2);
+        assert!(pokemon.fainted());
+
+        assert_eq!(pokemon.heal_fraction(1.0 / 16.0), max_hp / 16);
+        assert_eq!(pokemon.heal_fraction(2.0), max_hp - max_hp / 16);
+        assert_eq!(pokemon.hp, max_hp);
+    }
+
+    #[test]
+    fn pain_split_averages_hp() {
+        let dexes = Dexes::new();
+        let mut pidgey = dexes.owned(PIDGEY, 10);
+        let mut geodude = dexes.owned(GEODUDE, 50);
+        pidgey.hp = 10;
+        geodude.hp = 20;
+
+        pidgey.pain_split(&mut geodude);
+        assert_eq!((pidgey.hp, geodude.hp), (15, 15));
+
+        // neither pokemon goes over its max HP
+        geodude.hp = geodude.max_hp();
+        pidgey.pain_split(&mut geodude);
+        assert_eq!(pidgey.hp, pidgey.max_hp());
+        assert!(geodude.hp < geodude.max_hp());
+    }
+
+    #[test]
+    fn pain_split_does_not_overflow() {
+        let dexes = Dexes::new();
+        let mut pidgey = dexes.owned(PIDGEY, 10);
+        let mut geodude = dexes.owned(GEODUDE, 50);
+        pidgey.hp = Health::MAX;
+        geodude.hp = Health::MAX - 1;
+
+        pidgey.pain_split(&mut geodude);
+        assert_eq!((pidgey.hp, geodude.hp), (pidgey.max_hp(), geodude.max_hp()));
+    }
+
+    #[test]
+    fn height_and_weight_scale_with_size() {
+        let mut dexes = Dexes::new();
+        let pidgey = dexes.pokedex.inner_mut().get_mut(&PIDGEY).unwrap();
+        pidgey.height = 10;
+        pidgey.weight = 100;
+
+        let mut saved = Dexes::saved(PIDGEY, 5);
+        saved.scale = 0;
+        assert_eq!(saved.size_category(), SizeMark::XXS);
+        let small = dexes.init(saved.clone());
+        saved.scale = 255;
+        assert_eq!(saved.size_category(), SizeMark::XXL);
+        let large = dexes.init(saved);
+
+        assert!((small.height() - 8.0).abs() < 0.001);
+        assert!((small.weight() - 80.0).abs() < 0.001);
+        assert!((large.height() - 12.0).abs() < 0.001);
+        assert!((large.weight() - 120.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn generate_fixed_keeps_every_field() {
+        let ivs = stats(31, 30, 29, 28, 27, 26);
+        let pokemon =
+            OwnedIdPokemon::generate_fixed(PIDGEY, 12, Nature::Adamant, ivs, Some(Gender::Female), true);
+
+        assert_eq!(pokemon.pokemon, PIDGEY);
+        assert_eq!(pokemon.level, 12);
+        assert_eq!(pokemon.nature, Nature::Adamant);
+        assert_eq!(pokemon.ivs, ivs);
+        assert_eq!(pokemon.gender, Some(Gender::Female));
+        assert!(pokemon.shiny);
+        assert_eq!(pokemon.evs, Default::default());
+        assert_eq!(pokemon.friendship, Pokemon::default_friendship());
+        assert_eq!(pokemon.scale, SizeMark::default_scale());
+        // the same arguments always give the same pokemon
+        assert_eq!(
+            pokemon.checksum(),
+            OwnedIdPokemon::generate_fixed(PIDGEY, 12, Nature::Adamant, ivs, Some(Gender::Female), true)
+                .checksum()
+        );
+    }
+
+    #[test]
+    fn recommended_move_prefers_super_effective() {
+        let mut dexes = Dexes::new();
+        dexes.movedex.inner_mut().get_mut(&id("earthquake")).unwrap().power = Some(150);
+        let mut saved = Dexes::saved(PIDGEY, 20);
+        for m in ["growl", "earthquake", "gust"] {
+            saved.moves.push(id(m).into());
+        }
+        let mut pidgey = dexes.init(saved);
+        let bulbasaur = dexes.owned(BULBASAUR, 20);
+
+        // earthquake is neutral against grass/poison but does more damage than gust
+        assert!(pidgey.expected_damage(1, &bulbasaur, 1.0) > pidgey.expected_damage(2, &bulbasaur, 1.0));
+        assert_eq!(pidgey.recommended_move(&bulbasaur, 1.0), Some(2));
+
+        // moves without PP are skipped
+        pidgey.moves[2].pp = 0;
+        assert_eq!(pidgey.recommended_move(&bulbasaur, 1.0), Some(1));
+
+        // status moves are never recommended
+        pidgey.moves[1].pp = 0;
+        assert_eq!(pidgey.recommended_move(&bulbasaur, 1.0), None);
+    }
+
+    #[test]
+    fn stats_reflect_the_nature() {
+        let dexes = Dexes::new();
+        let with_nature = |nature| {
+            dexes.init(OwnedIdPokemon::generate_fixed(
+                PIDGEY,
+                50,
+                nature,
+                Stats::uniform(31),
+                None,
+                false,
+            ))
+        };
+        let (adamant, modest) = (with_nature(Nature::Adamant), with_nature(Nature::Modest));
+
+        assert!(adamant.stat(StatType::Attack) > modest.stat(StatType::Attack));
+        assert!(adamant.stat(StatType::SpAttack) < modest.stat(StatType::SpAttack));
+        assert_eq!(adamant.stat(StatType::Speed), modest.stat(StatType::Speed));
+        assert_eq!(adamant.max_hp(), modest.max_hp());
+
+        let hardy = with_nature(Nature::Hardy);
+        // 45 base attack at level 50 with 31 IVs is 65
+        assert_eq!(hardy.stat(StatType::Attack), 65);
+        assert_eq!(adamant.stat(StatType::Attack), 71);
+        assert_eq!(modest.stat(StatType::Attack), 58);
+    }
+
+    #[test]
+    fn level_evolution_after_leveling_up() {
+        let dexes = Dexes::new();
+        assert_eq!(dexes.owned(GEODUDE, 24).should_evolve(EvolutionTrigger::LevelUp), None);
+        let geodude = dexes.owned(GEODUDE, 25);
+        assert_eq!(geodude.should_evolve(EvolutionTrigger::LevelUp), Some(GRAVELER));
+        // a level evolution is not caused by using an item
+        assert_eq!(geodude.should_evolve(EvolutionTrigger::Item(id("potion"))), None);
+    }
+
+    #[test]
+    fn friendship_evolution_is_inclusive() {
+        let mut dexes = Dexes::new();
+        dexes.pokedex.inner_mut().get_mut(&PIDGEY).unwrap().evolutions = vec![Evolution {
+            into: BULBASAUR,
+            kind: EvolutionType::Friendship(220),
+        }];
+        let mut pidgey = dexes.owned(PIDGEY, 5);
+
+        pidgey.friendship = 219;
+        assert_eq!(pidgey.should_evolve(EvolutionTrigger::LevelUp), None);
+        pidgey.friendship = 220;
+        assert_eq!(pidgey.should_evolve(EvolutionTrigger::LevelUp), Some(BULBASAUR));
+    }
+
+    #[test]
+    fn stone_evolution_branches() {
+        let mut dexes = Dexes::new();
+        dexes.pokedex.inner_mut().get_mut(&PIDGEY).unwrap().evolutions = [
+            (BULBASAUR, "leaf_stone"),
+            (CHARMANDER, "fire_stone"),
+            (GASTLY, "moon_stone"),
+        ]
+        .iter()
+        .map(|(into, stone)| Evolution {
+            into: *into,
+            kind: EvolutionType::Stone(id(stone)),
+        })
+        .collect();
+        let pidgey = dexes.owned(PIDGEY, 5);
+
+        let stone = |stone| pidgey.should_evolve(EvolutionTrigger::Item(id(stone)));
+        assert_eq!(stone("leaf_stone"), Some(BULBASAUR));
+        assert_eq!(stone("fire_stone"), Some(CHARMANDER));
+        assert_eq!(stone("moon_stone"), Some(GASTLY));
+        assert_eq!(stone("water_stone"), None);
+        assert_eq!(pidgey.should_evolve(EvolutionTrigger::LevelUp), None);
+    }
+
+    #[test]
+    fn growth_rate_changes_leveling() {
+        let mut dexes = Dexes::new();
+        dexes.pokedex.inner_mut().get_mut(&PIDGEY).unwrap().training.growth_rate = GrowthRate::Fast;
+        dexes.pokedex.inner_mut().get_mut(&BULBASAUR).unwrap().training.growth_rate = GrowthRate::Slow;
+        let mut fast = dexes.owned(PIDGEY, 1);
+        let mut slow = dexes.owned(BULBASAUR, 1);
+
+        // experience is multiplied by 5, so both end up with 50,000
+        fast.add_exp(10_000).for_each(drop);
+        slow.add_exp(10_000).for_each(drop);
+        assert_eq!(fast.total_exp(), slow.total_exp());
+        assert_eq!(fast.level, 39);
+        assert_eq!(slow.level, 34);
+    }
+
+    #[test]
+    fn exp_to_next_level() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 10);
+        let rate = pokemon.pokemon.training.growth_rate;
+        assert_eq!(pokemon.experience, 0);
+        assert_eq!(pokemon.exp_to_next_level(), rate.max_exp(11) - rate.max_exp(10));
+        pokemon.experience += 1;
+        assert_eq!(pokemon.exp_to_next_level(), rate.max_exp(11) - rate.max_exp(10) - 1);
+
+        let pokemon = dexes.owned(PIDGEY, 100);
+        assert_eq!(pokemon.exp_to_next_level(), 0);
+    }
+
+    #[test]
+    fn add_exp_stops_at_level_100() {
+        let mut dexes = Dexes::new();
+        dexes
+            .pokedex
+            .inner_mut()
+            .get_mut(&PIDGEY)
+            .unwrap()
+            .moves
+            .push(LearnableMove(100, id("swift")));
+        let mut pokemon = dexes.owned(PIDGEY, 99);
+        let max = pokemon.pokemon.training.growth_rate.max_exp(100);
+
+        // pidgey has space for swift, so it is learned instead of being returned
+        assert_eq!(pokemon.add_exp(Experience::MAX).count(), 0);
+        assert!(pokemon.moves.iter().any(|m| m.m.id == id("swift")));
+        assert_eq!(pokemon.level, 100);
+        assert_eq!(pokemon.experience, 0);
+        assert_eq!(pokemon.total_exp(), max);
+
+        assert_eq!(pokemon.add_exp(Experience::MAX).count(), 0);
+        assert_eq!(pokemon.level, 100);
+        assert_eq!(pokemon.total_exp(), max);
+    }
+
+    #[test]
+    fn add_exp_keeps_progress_within_a_level() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 10);
+        let rate = pokemon.pokemon.training.growth_rate;
+
+        // 5 exp short of level 11, then 5 more than needed
+        pokemon.experience = rate.level_exp(10) - 5;
+        pokemon.add_exp(2).for_each(drop);
+        assert_eq!(pokemon.level, 11);
+        assert_eq!(pokemon.experience, 5);
+    }
+
+    #[test]
+    fn init_reconciles_level_from_saved_exp() {
+        let dexes = Dexes::new();
+        let rate = dexes.pokedex.try_get(&PIDGEY).unwrap().training.growth_rate;
+
+        // progress within the level is kept as it is
+        let mut saved = Dexes::saved(PIDGEY, 10);
+        saved.experience = rate.level_exp(10) - 1;
+        let pokemon = dexes.init(saved);
+        assert_eq!(pokemon.level, 10);
+        assert_eq!(pokemon.experience, rate.level_exp(10) - 1);
+
+        // a save holding more exp than its level allows is moved up without losing any
+        let mut saved = Dexes::saved(PIDGEY, 10);
+        saved.experience = rate.max_exp(20) - rate.max_exp(10) + 3;
+        let pokemon = dexes.init(saved);
+        assert_eq!(pokemon.level, 20);
+        assert_eq!(pokemon.experience, 3);
+        assert_eq!(pokemon.total_exp(), rate.max_exp(20) + 3);
+    }
+
+    #[test]
+    fn add_evs_stops_at_the_total_cap() {
+        let mut pokemon = Dexes::saved(PIDGEY, 50);
+        pokemon.evs = stats(252, 0, 252, 0, 0, 0);
+
+        let gains = Stats {
+            atk: Stat::MAX,
+            ..Default::default()
+        };
+        assert_eq!(pokemon.add_evs(&gains), 6);
+        assert_eq!(pokemon.evs.atk, 6);
+        assert_eq!(pokemon.evs.total(), Stats::MAX_EVS_TOTAL);
+        assert_eq!(pokemon.add_evs(&gains), 0);
+    }
+
+    #[test]
+    fn add_evs_fills_stats_in_order() {
+        let mut pokemon = Dexes::saved(PIDGEY, 50);
+        pokemon.evs = stats(250, 0, 0, 0, 0, 0);
+
+        // hp only has space for 2, and the rest of the total goes to attack first
+        assert_eq!(pokemon.add_evs(&Stats::uniform(Stat::MAX)), 260);
+        assert_eq!(pokemon.evs, stats(252, 252, 6, 0, 0, 0));
+    }
+
+    #[test]
+    fn ev_yield_from_defeated_pokemon() {
+        let machop = Pokemon {
+            training: Training {
+                ev_yield: stats(0, 1, 0, 0, 0, 0),
+                ..Default::default()
+            },
+            ..Pokemon::unknown()
+        };
+        let mut saved = Dexes::saved(PIDGEY, 5);
+        for _ in 0..3 {
+            assert_eq!(saved.gain_evs_from(&machop), 1);
+        }
+        assert_eq!(saved.evs, stats(0, 3, 0, 0, 0, 0));
+
+        // gains stop once the attack EVs are maxed
+        saved.evs.atk = Stats::MAX_EV;
+        assert_eq!(saved.gain_evs_from(&machop), 0);
+        assert_eq!(saved.evs.atk, Stats::MAX_EV);
+    }
+
+    #[test]
+    fn ev_yield_defaults_to_nothing() {
+        let training: Training = serde_json::from_str(r#"{ "base_exp": 64 }"#).unwrap();
+        assert_eq!(training.ev_yield, Stats::default());
+    }
+
+    #[test]
+    fn friendship_rises_on_level_up() {
+        let dexes = Dexes::new();
+        let mut pokemon = dexes.owned(PIDGEY, 5);
+        assert_eq!(pokemon.friendship, Pokemon::default_friendship());
+
+        pokemon.level = 10;
+        pokemon.on_level_up(5).for_each(drop);
+        assert_eq!(pokemon.friendship, Pokemon::default_friendship() + 5 * 5);
+
+        pokemon.friendship = 254;
+        pokemon.level = 20;
+        pokemon.on_level_up(10).for_each(drop);
+        assert_eq!(pokemon.friendship, Friendship::MAX);
+    }
+
+    #[test]
+    fn friendship_drops_on_faint() {
+        let mut pokemon = Dexes::saved(PIDGEY, 5);
+        pokemon.on_faint();
+        assert_eq!(pokemon.friendship, Pokemon::default_friendship() - 1);
+
+        pokemon.friendship = 0;
+        pokemon.on_faint();
+        assert_eq!(pokemon.friendship, 0);
+    }
+
+    #[test]
+    fn shiny_odds() {
+        let mut random = TestRng::new(1);
+        let generate = |random: &mut TestRng, odds| {
+            OwnedIdPokemon::generate_with_odds(random, PIDGEY, 5, None, None, odds)
+        };
+
+        // odds of 1 in 1 are always shiny
+        assert!((0..10).all(|_| generate(&mut random, 1).is_shiny()));
+        assert!(generate(&mut random, 0).is_shiny());
+        assert!(!(0..10).any(|_| generate(&mut random, u32::MAX).is_shiny()));
+
+        // the same seed gives the same pokemon
+        let shinies = |seed| {
+            let mut random = TestRng::new(seed);
+            (0..100)
+                .map(|_| generate(&mut random, 2).is_shiny())
+                .collect::<Vec<_>>()
+        };
+        let shiny = shinies(1);
+        assert_eq!(shiny, shinies(1));
+        assert!(shiny.contains(&true) && shiny.contains(&false));
+    }
+
+    #[test]
+    fn same_type_attack_bonus() {
+        let dexes = Dexes::new();
+        let charmander = dexes.owned(CHARMANDER, 5);
+        assert!((charmander.stab(PokemonType::Fire) - 1.5).abs() < f32::EPSILON);
+        assert!((charmander.stab(PokemonType::Water) - 1.0).abs() < f32::EPSILON);
+
+        let pidgey = dexes.owned(PIDGEY, 5);
+        // pidgey is normal/flying
+        assert!((pidgey.stab(PokemonType::Flying) - 1.5).abs() < f32::EPSILON);
+        assert!((pidgey.stab(PokemonType::Normal) - 1.5).abs() < f32::EPSILON);
+        assert!((pidgey.stab(PokemonType::Grass) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn relearnable_moves_list_skipped_moves() {
+        let mut dexes = Dexes::new();
+        dexes
+            .pokedex
+            .inner_mut()
+            .get_mut(&BULBASAUR)
+            .unwrap()
+            .moves
+            .push(LearnableMove(12, id("tackle")));
+        let mut pokemon = dexes.owned(BULBASAUR, 13);
+        // tackle at level 12 pushes growl out of the generated moves
+        assert_eq!(pokemon.relearnable_moves().collect::<Vec<_>>(), vec![&id("growl")]);
+
+        // tackle is learned at two levels, but only listed once
+        pokemon.moves.clear();
+        assert_eq!(
+            pokemon.relearnable_moves().collect::<Vec<_>>(),
+            vec![&id("tackle"), &id("growl"), &id("vine_whip"), &id("absorb"), &id("razor_leaf")]
+        );
+
+        // moves above the pokemon's level are not listed
+        pokemon.level = 8;
+        assert_eq!(
+            pokemon.relearnable_moves().collect::<Vec<_>>(),
+            vec![&id("tackle"), &id("growl"), &id("vine_whip")]
+        );
+
+        let pokemon = dexes.owned(BULBASAUR, 3);
+        assert_eq!(pokemon.relearnable_moves().count(), 0);
+    }
+}
@@ -0,0 +1,275 @@
+use hashbrown::HashSet;
+use rand::Rng;
+
+use crate::{
+    ailment::Ailment,
+    moves::MoveCategory,
+    pokemon::{OwnedIdPokemon, OwnedPokemon, OwnedRefPokemon, Pokerus},
+    types::PokemonType,
+};
+
+pub const PARTY_LENGTH: usize = 6;
+
+/// A party of pokemon. Holds [PARTY_LENGTH] pokemon by default.
+/// Helpers in this module take slices so they work with any party size.
+///
+/// Only the sizes arrayvec 0.5 has an array impl for can be used:
+/// 0 to 32, 40, 48, 50, 56, 64, 72, 96, 100, 128, 160, 192, 200 and 224.
+/// Other sizes do not compile:
+///
+/// ```compile_fail
+/// let party: firecore_pokedex::pokemon::Party<u8, 33> = Default::default();
+/// ```
+pub type Party<P, const N: usize = PARTY_LENGTH> = arrayvec::ArrayVec<[P; N]>;
+
+/// The pokemon at the front of the party.
+pub fn lead<P>(party: &[P]) -> Option<&P> {
+    party.first()
+}
+
+/// Moves a party member to the front, keeping the order of the rest. Does nothing if the index is out of range.
+pub fn set_lead<P>(party: &mut [P], index: usize) {
+    if index < party.len() {
+        party[..=index].rotate_right(1);
+    }
+}
+
+/// Swaps the held items of two party members. Does nothing if either index is out of range.
+pub fn swap_items<P, M, I, H>(party: &mut [OwnedPokemon<P, M, I, H>], a: usize, b: usize) {
+    if a != b && a < party.len() && b < party.len() {
+        let item = party[a].item.take();
+        party[a].item = core::mem::replace(&mut party[b].item, item);
+    }
+}
+
+/// Cures the ailments of every party member that has not fainted, such as for Heal Bell or Aromatherapy.
+/// Returns the index and ailment of each cured pokemon.
+pub fn cure_ailments(party: &mut [OwnedRefPokemon]) -> Vec<(usize, Ailment)> {
+    party
+        .iter_mut()
+        .enumerate()
+        .filter(|(.., pokemon)| !pokemon.fainted())
+        .filter_map(|(index, pokemon)| {
+            let ailment = pokemon.ailment?.ailment;
+            pokemon.cure_ailment(None).then_some((index, ailment))
+        })
+        .collect()
+}
+
+/// Gives party members next to an infected pokemon a chance to catch pokerus.
+pub fn spread_pokerus<P, M, I, H>(party: &mut [OwnedPokemon<P, M, I, H>], random: &mut impl Rng) {
+    let infected = party
+        .iter()
+        .enumerate()
+        .filter(|(.., pokemon)| pokemon.pokerus.is_infected())
+        .map(|(index, ..)| index)
+        .collect::<Vec<_>>();
+    for index in infected {
+        for neighbor in [index.wrapping_sub(1), index + 1] {
+            if let Some(pokemon) = party.get_mut(neighbor) {
+                if random.gen_range(0..Pokerus::INFECT_CHANCE) == 0 {
+                    pokemon.infect(random);
+                }
+            }
+        }
+    }
+}
+
+/// A checksum of the saved form of the party, used to detect corrupted saves.
+pub fn checksum(party: &[OwnedIdPokemon]) -> u64 {
+    crate::checksum::checksum(party)
+}
+
+/// Totals across every member of a party.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PartySummary {
+    pub total_level: u32,
+    pub average_level: f32,
+    /// The types of every damaging move known by the party.
+    pub type_coverage: HashSet<PokemonType>,
+    pub fainted: usize,
+}
+
+pub fn summary(party: &[OwnedRefPokemon]) -> PartySummary {
+    let total_level = party.iter().map(|pokemon| pokemon.level as u32).sum::<u32>();
+    PartySummary {
+        total_level,
+        average_level: match party.is_empty() {
+            true => 0.0,
+            false => total_level as f32 / party.len() as f32,
+        },
+        type_coverage: party
+            .iter()
+            .flat_map(|pokemon| pokemon.moves.iter())
+            .filter(|m| m.m.category != MoveCategory::Status)
+            .map(|m| m.m.pokemon_type)
+            .collect(),
+        fainted: party.iter().filter(|pokemon| pokemon.fainted()).count(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{checksum, cure_ailments, lead, set_lead, spread_pokerus, summary, swap_items, Party, PARTY_LENGTH};
+    use crate::{
+        ailment::{Ailment, LiveAilment},
+        pokemon::{OwnedIdPokemon, Pokerus},
+        testing::{id, Dexes, TestRng, BULBASAUR, PIDGEY},
+        types::PokemonType,
+    };
+
+    fn party() -> Party<OwnedIdPokemon> {
+        let mut party = Party::new();
+        party.push(Dexes::saved(BULBASAUR, 5));
+        party.push(Dexes::saved(PIDGEY, 3));
+        party
+    }
+
+    #[test]
+    fn identical_parties_have_the_same_checksum() {
+        assert_eq!(checksum(&party()), checksum(&party()));
+    }
+
+    #[test]
+    fn mutated_parties_have_another_checksum() {
+        let original = checksum(&party());
+
+        let mut mutated = party();
+        mutated[1].experience += 1;
+        assert_ne!(original, checksum(&mutated));
+
+        let mut mutated = party();
+        mutated[0].item = Some(id("potion"));
+        assert_ne!(original, checksum(&mutated));
+
+        let mut mutated = party();
+        mutated.swap(0, 1);
+        assert_ne!(original, checksum(&mutated));
+
+        let mut mutated = party();
+        mutated.pop();
+        assert_ne!(original, checksum(&mutated));
+    }
+
+    #[test]
+    fn default_party_size() {
+        let mut party: Party<OwnedIdPokemon> = Party::new();
+        assert_eq!(party.capacity(), PARTY_LENGTH);
+        for _ in 0..PARTY_LENGTH {
+            party.push(Dexes::saved(PIDGEY, 3));
+        }
+        assert!(party.is_full());
+        assert!(party.try_push(Dexes::saved(PIDGEY, 3)).is_err());
+    }
+
+    #[test]
+    fn custom_party_size() {
+        let mut party: Party<OwnedIdPokemon, 12> = Party::new();
+        assert_eq!(party.capacity(), 12);
+        for _ in 0..12 {
+            party.push(Dexes::saved(PIDGEY, 3));
+        }
+        assert!(party.try_push(Dexes::saved(PIDGEY, 3)).is_err());
+        // helpers take slices, so they work with any size
+        set_lead(&mut party, 11);
+        assert!(lead(&party).is_some());
+        assert_eq!(checksum(&party), checksum(&party.clone()));
+    }
+
+    #[test]
+    fn swap_items_with_a_pokemon_holding_nothing() {
+        let mut party = party();
+        party[0].item = Some(id("potion"));
+
+        swap_items(&mut party, 0, 1);
+        assert_eq!(party[0].item, None);
+        assert_eq!(party[1].item, Some(id("potion")));
+
+        party[0].item = Some(id("revive"));
+        swap_items(&mut party, 1, 0);
+        assert_eq!(party[0].item, Some(id("potion")));
+        assert_eq!(party[1].item, Some(id("revive")));
+
+        // out of range indices do nothing
+        swap_items(&mut party, 0, 2);
+        assert_eq!(party[0].item, Some(id("potion")));
+    }
+
+    #[test]
+    fn pokerus_spreads_to_neighbors() {
+        let mut party = party();
+        party.push(Dexes::saved(PIDGEY, 3));
+        party[0].pokerus = Pokerus::Infected { days_left: 4 };
+
+        let mut random = TestRng::new(5);
+        assert!((0..100).any(|_| {
+            spread_pokerus(&mut party, &mut random);
+            party[1].pokerus.is_infected()
+        }));
+        // the third pokemon can only catch it from the second
+        party[1].pokerus = Pokerus::Cured;
+        party[2].pokerus = Pokerus::None;
+        for _ in 0..100 {
+            spread_pokerus(&mut party, &mut random);
+        }
+        assert_eq!(party[2].pokerus, Pokerus::None);
+    }
+
+    #[test]
+    fn set_lead_shifts_the_others_down() {
+        let mut party: Party<u8> = (0..5).collect();
+        set_lead(&mut party, 3);
+        assert_eq!(party.as_slice(), &[3, 0, 1, 2, 4]);
+        assert_eq!(lead(&party), Some(&3));
+
+        set_lead(&mut party, 5);
+        assert_eq!(party.as_slice(), &[3, 0, 1, 2, 4]);
+        assert_eq!(lead(&Party::<u8>::new()), None);
+    }
+
+    #[test]
+    fn party_summary() {
+        let dexes = Dexes::new();
+        let mut party = vec![dexes.owned(BULBASAUR, 7), dexes.owned(PIDGEY, 5)];
+        party[1].hp = 0;
+
+        let summary = summary(&party);
+        assert_eq!(summary.total_level, 12);
+        assert!((summary.average_level - 6.0).abs() < f32::EPSILON);
+        assert_eq!(summary.fainted, 1);
+        // growl is a status move and does not count towards coverage
+        assert_eq!(
+            summary.type_coverage,
+            [PokemonType::Normal, PokemonType::Grass, PokemonType::Flying]
+                .iter()
+                .copied()
+                .collect()
+        );
+
+        assert_eq!(super::summary(&[]).total_level, 0);
+        assert!(super::summary(&[]).average_level.abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn heal_bell_cures_the_bench() {
+        let dexes = Dexes::new();
+        let mut party = vec![
+            dexes.owned(BULBASAUR, 5),
+            dexes.owned(PIDGEY, 3),
+            dexes.owned(PIDGEY, 4),
+        ];
+        let poison = Some(LiveAilment {
+            ailment: Ailment::Poison,
+            turns: None,
+        });
+        party[1].ailment = poison;
+        party[2].ailment = poison;
+        party[2].hp = 0;
+
+        assert_eq!(cure_ailments(&mut party), vec![(1, Ailment::Poison)]);
+        assert!(party[1].ailment.is_none());
+        // fainted pokemon are not cured
+        assert!(party[2].ailment.is_some());
+        assert!(cure_ailments(&mut party).is_empty());
+    }
+}
@@ -1,13 +1,18 @@
-use core::ops::Range;
+use core::ops::RangeInclusive;
 
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
+use crate::{moves::Power, pokemon::Level, types::PokemonType};
+
 pub type Stat = u8;
 pub type BaseStat = u16;
 
 pub type Stats = StatSet<Stat>;
 
+/// Final calculated stats of a pokemon, where `hp` is the maximum health.
+pub type StatSnapshot = StatSet<BaseStat>;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum StatType {
     Health,
@@ -21,7 +26,18 @@ pub enum StatType {
     Speed,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+impl StatType {
+    pub const ALL: [StatType; 6] = [
+        StatType::Health,
+        StatType::Attack,
+        StatType::Defense,
+        StatType::SpAttack,
+        StatType::SpDefense,
+        StatType::Speed,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub struct StatSet<S> {
     pub hp: S,
     pub atk: S,
@@ -42,26 +58,8 @@ impl<S: Copy> StatSet<S> {
             speed: stat,
         }
     }
-}
-
-impl Stats {
-    pub const MAX_EV: Stat = 32;
-    pub const MAX_IV: Stat = 252;
-    pub const MAX_IVS_TOTAL: u16 = 512;
-    pub const EV_RANGE: Range<Stat> = 0..Self::MAX_EV;
-
-    pub fn random(random: &mut impl Rng) -> Self {
-        Self {
-            hp: random.gen_range(Self::EV_RANGE),
-            atk: random.gen_range(Self::EV_RANGE),
-            def: random.gen_range(Self::EV_RANGE),
-            sp_atk: random.gen_range(Self::EV_RANGE),
-            sp_def: random.gen_range(Self::EV_RANGE),
-            speed: random.gen_range(Self::EV_RANGE),
-        }
-    }
 
-    pub fn get(&self, stat: StatType) -> Stat {
+    pub fn get(&self, stat: StatType) -> S {
         match stat {
             StatType::Health => self.hp,
             StatType::Attack => self.atk,
@@ -72,7 +70,331 @@ impl Stats {
         }
     }
 
+    pub fn get_mut(&mut self, stat: StatType) -> &mut S {
+        match stat {
+            StatType::Health => &mut self.hp,
+            StatType::Attack => &mut self.atk,
+            StatType::Defense => &mut self.def,
+            StatType::SpAttack => &mut self.sp_atk,
+            StatType::SpDefense => &mut self.sp_def,
+            StatType::Speed => &mut self.speed,
+        }
+    }
+}
+
+impl Stats {
+    pub const MAX_EV: Stat = 252;
+    pub const MAX_IV: Stat = 31;
+    pub const MAX_EVS_TOTAL: u16 = 510;
+    pub const EV_RANGE: RangeInclusive<Stat> = 0..=Self::MAX_EV;
+    pub const IV_RANGE: RangeInclusive<Stat> = 0..=Self::MAX_IV;
+
+    pub fn random(random: &mut impl Rng) -> Self {
+        Self {
+            hp: random.gen_range(Self::IV_RANGE),
+            atk: random.gen_range(Self::IV_RANGE),
+            def: random.gen_range(Self::IV_RANGE),
+            sp_atk: random.gen_range(Self::IV_RANGE),
+            sp_def: random.gen_range(Self::IV_RANGE),
+            speed: random.gen_range(Self::IV_RANGE),
+        }
+    }
+
     pub fn default_iv() -> Self {
         Self::uniform(15)
     }
+
+    pub fn total(&self) -> u16 {
+        StatType::ALL.iter().map(|stat| self.get(*stat) as u16).sum()
+    }
+
+    /// Adds EVs to a stat without going over [Stats::MAX_EV] or the remaining total.
+    /// Returns how many EVs were added.
+    pub fn add_ev_clamped(&mut self, stat: StatType, amount: Stat, total_remaining: u16) -> Stat {
+        let ev = self.get_mut(stat);
+        let gain = amount
+            .min(Self::MAX_EV.saturating_sub(*ev))
+            .min(total_remaining.min(Stat::MAX as u16) as Stat);
+        *ev += gain;
+        gain
+    }
+
+    /// Clamps each EV to [Stats::MAX_EV] and the total to [Stats::MAX_EVS_TOTAL],
+    /// keeping earlier stats first. Used to sanitize EVs from saves.
+    pub fn clamp_evs(&mut self) {
+        let evs = core::mem::take(self);
+        let mut remaining = Self::MAX_EVS_TOTAL;
+        for stat in StatType::ALL {
+            remaining -= self.add_ev_clamped(stat, evs.get(stat), remaining) as u16;
+        }
+    }
+
+    /// Get the type and power of Hidden Power for these IVs (Gen 3 - 5 formula).
+    pub fn hidden_power(&self) -> (PokemonType, Power) {
+        const TYPES: [PokemonType; 16] = [
+            PokemonType::Fighting,
+            PokemonType::Flying,
+            PokemonType::Poison,
+            PokemonType::Ground,
+            PokemonType::Rock,
+            PokemonType::Bug,
+            PokemonType::Ghost,
+            PokemonType::Steel,
+            PokemonType::Fire,
+            PokemonType::Water,
+            PokemonType::Grass,
+            PokemonType::Electric,
+            PokemonType::Psychic,
+            PokemonType::Ice,
+            PokemonType::Dragon,
+            PokemonType::Dark,
+        ];
+
+        let bits = |bit: u8| {
+            [self.hp, self.atk, self.def, self.speed, self.sp_atk, self.sp_def]
+                .iter()
+                .enumerate()
+                .map(|(index, iv)| (((iv >> bit) & 1) as u16) << index)
+                .sum::<u16>()
+        };
+
+        let pokemon_type = TYPES[(bits(0) * 15 / 63) as usize];
+        let power = (bits(1) * 40 / 63) as Power + 30;
+        (pokemon_type, power)
+    }
+
+    /// Rates each IV like the in-game IV judge.
+    pub fn judge(&self) -> StatSet<IvRating> {
+        StatSet {
+            hp: IvRating::of(self.hp),
+            atk: IvRating::of(self.atk),
+            def: IvRating::of(self.def),
+            sp_atk: IvRating::of(self.sp_atk),
+            sp_def: IvRating::of(self.sp_def),
+            speed: IvRating::of(self.speed),
+        }
+    }
+
+    /// Rates the total of all IVs like the in-game IV judge.
+    pub fn iv_total_rating(&self) -> IvTotalRating {
+        let total = [self.hp, self.atk, self.def, self.sp_atk, self.sp_def, self.speed]
+            .iter()
+            .map(|iv| *iv as u16)
+            .sum::<u16>();
+        match total {
+            0..=90 => IvTotalRating::Decent,
+            91..=120 => IvTotalRating::AboveAverage,
+            121..=150 => IvTotalRating::RelativelySuperior,
+            _ => IvTotalRating::Outstanding,
+        }
+    }
+
+    pub fn iv_summary(&self) -> IvSummary {
+        let (hidden_power_type, hidden_power_power) = self.hidden_power();
+        IvSummary {
+            values: *self,
+            hidden_power_type,
+            hidden_power_power,
+        }
+    }
+}
+
+/// The formula used to calculate a pokemon's stats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+pub enum StatFormula {
+    /// Generation 1 and 2 formula. IVs are used as DVs (0 - 15) and EVs as the square root of stat experience.
+    Gen1,
+    /// Generation 3 and onwards formula.
+    #[default]
+    Gen3,
+}
+
+impl StatFormula {
+    pub fn stat(self, base: Stat, iv: Stat, ev: Stat, level: Level) -> BaseStat {
+        match self {
+            StatFormula::Gen1 => Self::gen1(base, iv, ev, level) + 5,
+            StatFormula::Gen3 => Self::gen3(base, iv, ev, level) + 5,
+        }
+    }
+
+    pub fn hp(self, base: Stat, iv: Stat, ev: Stat, level: Level) -> BaseStat {
+        match self {
+            StatFormula::Gen1 => Self::gen1(base, iv, ev, level) + level as BaseStat + 10,
+            StatFormula::Gen3 => Self::gen3(base, iv, ev, level) + level as BaseStat + 10,
+        }
+    }
+
+    fn gen1(base: Stat, iv: Stat, ev: Stat, level: Level) -> BaseStat {
+        (((base as u32 + iv as u32) * 2 + ev as u32 / 4) * level as u32 / 100) as BaseStat
+    }
+
+    fn gen3(base: Stat, iv: Stat, ev: Stat, level: Level) -> BaseStat {
+        ((2 * base as u32 + iv as u32 + ev as u32 / 4) * level as u32 / 100) as BaseStat
+    }
+}
+
+/// The IV judge's rating of a single IV.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum IvRating {
+    /// 0
+    NoGood,
+    /// 1 - 15
+    Decent,
+    /// 16 - 25
+    PrettyGood,
+    /// 26 - 29
+    VeryGood,
+    /// 30
+    Fantastic,
+    /// 31
+    Best,
+}
+
+impl IvRating {
+    pub const fn of(iv: Stat) -> Self {
+        match iv {
+            0 => Self::NoGood,
+            1..=15 => Self::Decent,
+            16..=25 => Self::PrettyGood,
+            26..=29 => Self::VeryGood,
+            30 => Self::Fantastic,
+            _ => Self::Best,
+        }
+    }
+}
+
+/// The IV judge's rating of the total of a pokemon's IVs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum IvTotalRating {
+    /// 0 - 90
+    Decent,
+    /// 91 - 120
+    AboveAverage,
+    /// 121 - 150
+    RelativelySuperior,
+    /// 151 - 186
+    Outstanding,
+}
+
+/// A readout of a pokemon's IVs for debug screens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IvSummary {
+    pub values: Stats,
+    pub hidden_power_type: PokemonType,
+    pub hidden_power_power: Power,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gen3_formula() {
+        assert_eq!(StatFormula::Gen3.stat(100, 31, 0, 100), 236);
+        assert_eq!(StatFormula::Gen3.stat(100, 31, 0, 50), 120);
+        assert_eq!(StatFormula::Gen3.hp(100, 31, 0, 100), 341);
+        assert_eq!(StatFormula::default(), StatFormula::Gen3);
+    }
+
+    #[test]
+    fn gen3_formula_quarters_evs() {
+        assert_eq!(StatFormula::Gen3.stat(100, 31, 252, 100), 299);
+        assert_eq!(StatFormula::Gen3.hp(100, 31, 252, 100), 404);
+        assert_eq!(StatFormula::Gen3.stat(100, 31, 252, 50), 152);
+        // EVs below 4 do nothing
+        assert_eq!(StatFormula::Gen3.stat(100, 31, 3, 100), StatFormula::Gen3.stat(100, 31, 0, 100));
+    }
+
+    #[test]
+    fn gen1_formula() {
+        assert_eq!(StatFormula::Gen1.stat(100, 15, 0, 100), 235);
+        assert_eq!(StatFormula::Gen1.hp(100, 15, 0, 100), 340);
+        // does not overflow with maxed values
+        assert_eq!(StatFormula::Gen1.stat(255, 15, 255, 100), 608);
+    }
+
+    #[test]
+    fn hidden_power_from_ivs() {
+        assert_eq!(Stats::uniform(31).hidden_power(), (PokemonType::Dark, 70));
+        assert_eq!(Stats::uniform(0).hidden_power(), (PokemonType::Fighting, 30));
+
+        let fire = Stats {
+            hp: 31,
+            atk: 30,
+            def: 31,
+            sp_atk: 30,
+            sp_def: 31,
+            speed: 30,
+        };
+        let summary = fire.iv_summary();
+        assert_eq!(summary.values, fire);
+        assert_eq!(summary.hidden_power_type, PokemonType::Fire);
+        assert_eq!(summary.hidden_power_power, 70);
+    }
+
+    #[test]
+    fn iv_judge() {
+        assert_eq!(IvRating::of(31), IvRating::Best);
+        assert_eq!(IvRating::of(30), IvRating::Fantastic);
+        assert_eq!(IvRating::of(26), IvRating::VeryGood);
+        assert_eq!(IvRating::of(25), IvRating::PrettyGood);
+        assert_eq!(IvRating::of(1), IvRating::Decent);
+        assert_eq!(IvRating::of(0), IvRating::NoGood);
+
+        let ivs = Stats {
+            hp: 31,
+            atk: 0,
+            ..Stats::uniform(15)
+        };
+        let judged = ivs.judge();
+        assert_eq!(judged.hp, IvRating::Best);
+        assert_eq!(judged.atk, IvRating::NoGood);
+        assert_eq!(judged.speed, IvRating::Decent);
+
+        assert_eq!(Stats::uniform(15).iv_total_rating(), IvTotalRating::Decent);
+        assert_eq!(Stats::uniform(20).iv_total_rating(), IvTotalRating::AboveAverage);
+        assert_eq!(Stats::uniform(25).iv_total_rating(), IvTotalRating::RelativelySuperior);
+        assert_eq!(Stats::uniform(31).iv_total_rating(), IvTotalRating::Outstanding);
+    }
+
+    #[test]
+    fn ev_per_stat_cap() {
+        let mut evs = Stats {
+            atk: 250,
+            ..Default::default()
+        };
+        assert_eq!(evs.add_ev_clamped(StatType::Attack, 10, Stats::MAX_EVS_TOTAL), 2);
+        assert_eq!(evs.atk, Stats::MAX_EV);
+        assert_eq!(evs.add_ev_clamped(StatType::Attack, 10, Stats::MAX_EVS_TOTAL), 0);
+
+        // the remaining total limits the gain too
+        assert_eq!(evs.add_ev_clamped(StatType::Speed, 10, 4), 4);
+        assert_eq!(evs.speed, 4);
+    }
+
+    #[test]
+    fn clamp_illegal_evs() {
+        let mut evs = Stats::uniform(255);
+        evs.clamp_evs();
+        assert_eq!(
+            evs,
+            Stats {
+                hp: 252,
+                atk: 252,
+                def: 6,
+                sp_atk: 0,
+                sp_def: 0,
+                speed: 0,
+            }
+        );
+
+        let mut legal = Stats {
+            hp: 100,
+            speed: 252,
+            ..Default::default()
+        };
+        let before = legal;
+        legal.clamp_evs();
+        assert_eq!(legal, before);
+    }
 }
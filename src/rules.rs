@@ -0,0 +1,105 @@
+use core::ops::RangeInclusive;
+use hashbrown::HashSet;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    item::ItemId,
+    moves::MoveId,
+    pokemon::{Level, OwnedIdPokemon, PokemonId},
+};
+
+/// Restrictions a team must follow to be valid for a format.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Ruleset {
+    /// The levels party members are allowed to be.
+    #[serde(default)]
+    pub levels: Option<RangeInclusive<Level>>,
+    #[serde(default)]
+    pub banned_species: HashSet<PokemonId>,
+    #[serde(default)]
+    pub banned_items: HashSet<ItemId>,
+    #[serde(default)]
+    pub banned_moves: HashSet<MoveId>,
+}
+
+/// A rule broken by the team member at the given index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleViolation {
+    Level(usize, Level),
+    BannedSpecies(usize, PokemonId),
+    BannedItem(usize, ItemId),
+    BannedMove(usize, MoveId),
+}
+
+impl Ruleset {
+    /// Lists every rule the team breaks. A valid team has no violations.
+    pub fn validate_team(&self, team: &[OwnedIdPokemon]) -> Vec<RuleViolation> {
+        let mut violations = Vec::new();
+        for (index, pokemon) in team.iter().enumerate() {
+            if let Some(levels) = &self.levels {
+                if !levels.contains(&pokemon.level) {
+                    violations.push(RuleViolation::Level(index, pokemon.level));
+                }
+            }
+            if self.banned_species.contains(&pokemon.pokemon) {
+                violations.push(RuleViolation::BannedSpecies(index, pokemon.pokemon));
+            }
+            if let Some(item) = pokemon.item.filter(|item| self.banned_items.contains(item)) {
+                violations.push(RuleViolation::BannedItem(index, item));
+            }
+            violations.extend(
+                pokemon
+                    .moves
+                    .iter()
+                    .filter(|m| self.banned_moves.contains(&m.m))
+                    .map(|m| RuleViolation::BannedMove(index, m.m)),
+            );
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RuleViolation, Ruleset};
+    use crate::testing::{id, Dexes, BULBASAUR, PIDGEY};
+
+    #[test]
+    fn banned_moves_are_rejected() {
+        let mut pidgey = Dexes::saved(PIDGEY, 10);
+        pidgey.moves.push(id("tackle").into());
+        pidgey.moves.push(id("gust").into());
+        let team = [Dexes::saved(BULBASAUR, 10), pidgey];
+
+        let mut rules = Ruleset::default();
+        assert!(rules.validate_team(&team).is_empty());
+
+        rules.banned_moves.insert(id("gust"));
+        assert_eq!(
+            rules.validate_team(&team),
+            vec![RuleViolation::BannedMove(1, id("gust"))]
+        );
+    }
+
+    #[test]
+    fn levels_species_and_items() {
+        let mut bulbasaur = Dexes::saved(BULBASAUR, 60);
+        bulbasaur.item = Some(id("kings_rock"));
+        let team = [Dexes::saved(PIDGEY, 50), bulbasaur];
+
+        let rules = Ruleset {
+            levels: Some(1..=50),
+            banned_species: [BULBASAUR].iter().copied().collect(),
+            banned_items: [id("kings_rock")].iter().copied().collect(),
+            ..Default::default()
+        };
+        assert_eq!(
+            rules.validate_team(&team),
+            vec![
+                RuleViolation::Level(1, 60),
+                RuleViolation::BannedSpecies(1, BULBASAUR),
+                RuleViolation::BannedItem(1, id("kings_rock")),
+            ]
+        );
+    }
+}
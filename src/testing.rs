@@ -0,0 +1,298 @@
+//! Fixtures shared by the unit tests.
+
+use hashbrown::HashMap;
+use rand::RngCore;
+use tinystr::TinyStr16;
+
+use crate::{
+    ailment::Ailment,
+    item::{
+        usage::{ItemAction, ItemCondition, ItemUsage, ItemUsageKind},
+        Item, ItemCategory, Itemdex,
+    },
+    moves::{Accuracy, Move, MoveCategory, Movedex, Power, PP},
+    pokemon::{
+        stat::Stats, Evolution, EvolutionType, Level, LearnableMove, OwnedIdPokemon, OwnedRefPokemon, Pokedex, Pokemon,
+        PokemonId,
+    },
+    types::PokemonType,
+    Dex, Identifiable,
+};
+
+pub const BULBASAUR: PokemonId = 1;
+pub const CHARMANDER: PokemonId = 4;
+pub const PIDGEY: PokemonId = 16;
+pub const GEODUDE: PokemonId = 74;
+pub const GRAVELER: PokemonId = 75;
+pub const GASTLY: PokemonId = 92;
+pub const SKARMORY: PokemonId = 227;
+
+/// Move and item ids are both [TinyStr16]s.
+pub fn id(id: &str) -> TinyStr16 {
+    id.parse().unwrap()
+}
+
+pub fn dex<I: Identifiable>(entries: impl IntoIterator<Item = I>) -> Dex<I> {
+    Dex::new(
+        entries
+            .into_iter()
+            .map(|entry| (*entry.id(), entry))
+            .collect::<HashMap<_, _>>(),
+    )
+}
+
+/// A small xorshift generator so tests are repeatable.
+pub struct TestRng(u64);
+
+impl TestRng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+}
+
+impl RngCore for TestRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        rand_core_fill(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        rand_core_fill(self, dest);
+        Ok(())
+    }
+}
+
+fn rand_core_fill(rng: &mut TestRng, dest: &mut [u8]) {
+    for chunk in dest.chunks_mut(8) {
+        let bytes = rng.next_u64().to_le_bytes();
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+}
+
+pub fn stats(hp: u8, atk: u8, def: u8, sp_atk: u8, sp_def: u8, speed: u8) -> Stats {
+    Stats {
+        hp,
+        atk,
+        def,
+        sp_atk,
+        sp_def,
+        speed,
+    }
+}
+
+pub fn species(
+    id_: PokemonId,
+    name: &str,
+    primary_type: PokemonType,
+    secondary_type: Option<PokemonType>,
+    base: Stats,
+    moves: &[(Level, &str)],
+) -> Pokemon {
+    Pokemon {
+        secondary_type,
+        base,
+        moves: moves
+            .iter()
+            .map(|(level, m)| LearnableMove(*level, id(m)))
+            .collect(),
+        ..Pokemon::new(id_, name.to_owned(), primary_type)
+    }
+}
+
+pub fn move_with(
+    name: &str,
+    pokemon_type: PokemonType,
+    category: MoveCategory,
+    power: Option<Power>,
+    accuracy: Option<Accuracy>,
+    pp: PP,
+) -> Move {
+    Move {
+        id: id(name),
+        name: name.to_owned(),
+        category,
+        pokemon_type,
+        power,
+        accuracy,
+        pp,
+        ..Move::unknown()
+    }
+}
+
+pub fn item_with(name: &str, conditions: Vec<ItemCondition>, actions: Vec<ItemAction>) -> Item {
+    Item {
+        id: id(name),
+        name: name.to_owned(),
+        description: Vec::new(),
+        category: ItemCategory::Items,
+        stack_size: Item::default_stack_size(),
+        usage: ItemUsage {
+            conditions,
+            kind: match actions.is_empty() {
+                true => ItemUsageKind::None,
+                false => ItemUsageKind::Actions(actions),
+            },
+            consume: true,
+        },
+        fling_power: None,
+        fling_effect: None,
+    }
+}
+
+pub fn pokedex() -> Pokedex {
+    use PokemonType::*;
+    dex(vec![
+        Pokemon::unknown(),
+        species(
+            BULBASAUR,
+            "Bulbasaur",
+            Grass,
+            Some(Poison),
+            stats(45, 49, 49, 65, 65, 45),
+            &[(1, "tackle"), (3, "growl"), (7, "vine_whip"), (9, "absorb"), (13, "razor_leaf")],
+        ),
+        species(
+            CHARMANDER,
+            "Charmander",
+            Fire,
+            None,
+            stats(39, 52, 43, 60, 50, 65),
+            &[(1, "scratch"), (1, "growl"), (7, "ember")],
+        ),
+        species(
+            PIDGEY,
+            "Pidgey",
+            Normal,
+            Some(Flying),
+            stats(40, 45, 40, 35, 35, 56),
+            &[(1, "tackle"), (5, "gust")],
+        ),
+        Pokemon {
+            evolutions: vec![Evolution {
+                into: GRAVELER,
+                kind: EvolutionType::Level(25),
+            }],
+            ..species(
+                GEODUDE,
+                "Geodude",
+                Rock,
+                Some(Ground),
+                stats(40, 80, 100, 30, 30, 20),
+                &[(1, "tackle"), (6, "earthquake")],
+            )
+        },
+        species(
+            GRAVELER,
+            "Graveler",
+            Rock,
+            Some(Ground),
+            stats(55, 95, 115, 45, 45, 35),
+            &[(1, "tackle"), (6, "earthquake")],
+        ),
+        species(
+            GASTLY,
+            "Gastly",
+            Ghost,
+            Some(Poison),
+            stats(30, 35, 30, 100, 35, 80),
+            &[(1, "tackle")],
+        ),
+        species(
+            SKARMORY,
+            "Skarmory",
+            Steel,
+            Some(Flying),
+            stats(65, 80, 140, 40, 70, 70),
+            &[(1, "tackle")],
+        ),
+    ])
+}
+
+pub fn movedex() -> Movedex {
+    use MoveCategory::*;
+    use PokemonType::*;
+    dex(vec![
+        Move::unknown(),
+        move_with("tackle", Normal, Physical, Some(40), Some(100), 35),
+        move_with("scratch", Normal, Physical, Some(40), Some(100), 35),
+        move_with("growl", Normal, Status, None, Some(100), 40),
+        move_with("vine_whip", Grass, Physical, Some(45), Some(100), 25),
+        move_with("absorb", Grass, Special, Some(20), Some(100), 25),
+        move_with("razor_leaf", Grass, Physical, Some(55), Some(95), 25),
+        move_with("ember", Fire, Special, Some(40), Some(100), 25),
+        move_with("gust", Flying, Special, Some(40), Some(100), 35),
+        move_with("earthquake", Ground, Physical, Some(100), Some(100), 10),
+        move_with("swift", Normal, Special, Some(60), None, 20),
+        move_with("mega_punch", Normal, Physical, Some(80), Some(50), 20),
+    ])
+}
+
+pub fn itemdex() -> Itemdex {
+    dex(vec![
+        item_with("potion", vec![], vec![ItemAction::HealPokemon(20)]),
+        item_with(
+            "revive",
+            vec![ItemCondition::Fainted],
+            vec![ItemAction::HealPokemon(u16::MAX)],
+        ),
+        item_with("ether", vec![], vec![ItemAction::RestorePP(Some(10))]),
+        item_with(
+            "antidote",
+            vec![],
+            vec![ItemAction::CurePokemon(Some(Ailment::Poison))],
+        ),
+        item_with("full_heal", vec![], vec![ItemAction::CurePokemon(None)]),
+        Item {
+            fling_power: Some(30),
+            ..item_with("kings_rock", vec![], vec![])
+        },
+    ])
+}
+
+pub struct Dexes {
+    pub pokedex: Pokedex,
+    pub movedex: Movedex,
+    pub itemdex: Itemdex,
+}
+
+impl Dexes {
+    pub fn new() -> Self {
+        Self {
+            pokedex: pokedex(),
+            movedex: movedex(),
+            itemdex: itemdex(),
+        }
+    }
+
+    /// A saved pokemon with neutral IVs, no EVs and a Hardy nature.
+    pub fn saved(pokemon: PokemonId, level: Level) -> OwnedIdPokemon {
+        OwnedIdPokemon::generate_fixed(
+            pokemon,
+            level,
+            Default::default(),
+            Stats::default_iv(),
+            None,
+            false,
+        )
+    }
+
+    pub fn owned(&self, pokemon: PokemonId, level: Level) -> OwnedRefPokemon<'_> {
+        self.init(Self::saved(pokemon, level))
+    }
+
+    pub fn init(&self, saved: OwnedIdPokemon) -> OwnedRefPokemon<'_> {
+        saved
+            .init(&mut TestRng::new(1), &self.pokedex, &self.movedex, &self.itemdex)
+            .unwrap()
+    }
+}
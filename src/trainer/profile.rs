@@ -0,0 +1,77 @@
+use hashbrown::HashSet;
+use serde::{Deserialize, Serialize};
+
+use crate::pokemon::{Level, OwnedIdPokemon, PokemonId};
+
+pub type TrainerId = u32;
+pub type Money = u32;
+
+/// The information shown on a trainer card.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TrainerProfile {
+    pub id: TrainerId,
+    pub name: String,
+    #[serde(default)]
+    pub party: Vec<OwnedIdPokemon>,
+    #[serde(default)]
+    pub money: Money,
+    #[serde(default)]
+    pub badges: u8,
+    #[serde(default)]
+    pub pokedex: SeenCaught,
+}
+
+impl TrainerProfile {
+    pub fn party_preview(&self) -> Vec<(PokemonId, Level)> {
+        self.party
+            .iter()
+            .map(|pokemon| (pokemon.pokemon, pokemon.level))
+            .collect()
+    }
+}
+
+/// The pokemon a trainer has seen and caught.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SeenCaught {
+    pub seen: HashSet<PokemonId>,
+    pub caught: HashSet<PokemonId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SeenCaught, TrainerProfile};
+    use crate::testing::{Dexes, BULBASAUR, PIDGEY};
+
+    #[test]
+    fn profile_round_trip() {
+        let profile = TrainerProfile {
+            id: 12345,
+            name: "Red".to_owned(),
+            party: vec![Dexes::saved(BULBASAUR, 5), Dexes::saved(PIDGEY, 3)],
+            money: 3000,
+            badges: 2,
+            pokedex: SeenCaught {
+                seen: [BULBASAUR, PIDGEY].iter().copied().collect(),
+                caught: [BULBASAUR].iter().copied().collect(),
+            },
+        };
+        assert_eq!(profile.party_preview(), vec![(BULBASAUR, 5), (PIDGEY, 3)]);
+
+        let json = serde_json::to_string(&profile).unwrap();
+        let loaded: TrainerProfile = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.id, profile.id);
+        assert_eq!(loaded.name, profile.name);
+        assert_eq!(loaded.money, profile.money);
+        assert_eq!(loaded.badges, profile.badges);
+        assert_eq!(loaded.pokedex, profile.pokedex);
+        assert_eq!(loaded.party_preview(), profile.party_preview());
+    }
+
+    #[test]
+    fn optional_fields() {
+        let profile: TrainerProfile = serde_json::from_str(r#"{ "id": 1, "name": "Blue" }"#).unwrap();
+        assert!(profile.party_preview().is_empty());
+        assert_eq!(profile.money, 0);
+        assert_eq!(profile.pokedex, SeenCaught::default());
+    }
+}
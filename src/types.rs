@@ -1,13 +1,18 @@
 use core::{
     fmt::{Display, Formatter, Result as FmtResult},
     ops::Mul,
+    str::FromStr,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::moves::MoveCategory;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+mod chart;
+pub use chart::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
 pub enum PokemonType {
+    #[default]
     Unknown,
 
     Normal,
@@ -30,26 +35,95 @@ pub enum PokemonType {
     Fairy,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Deserialize, Serialize)]
 pub enum Effective {
+    #[default]
     Effective,
     Ineffective,
     NotEffective,
     SuperEffective,
+    /// Not very effective against both of a pokemon's types (0.25x).
+    DoubleNotEffective,
+    /// Super effective against both of a pokemon's types (4x).
+    DoubleSuperEffective,
 }
 
 impl Effective {
     pub const fn multiplier(self) -> f32 {
         match self {
             Effective::Ineffective => 0.0,
+            Effective::DoubleNotEffective => 0.25,
             Effective::NotEffective => 0.5,
             Effective::Effective => 1.0,
             Effective::SuperEffective => 2.0,
+            Effective::DoubleSuperEffective => 4.0,
+        }
+    }
+
+    pub const fn is_super_effective(self) -> bool {
+        matches!(self, Effective::SuperEffective | Effective::DoubleSuperEffective)
+    }
+
+    /// The power of two of the multiplier, or [None] for immunity.
+    const fn exponent(self) -> Option<i8> {
+        match self {
+            Effective::Ineffective => None,
+            Effective::DoubleNotEffective => Some(-2),
+            Effective::NotEffective => Some(-1),
+            Effective::Effective => Some(0),
+            Effective::SuperEffective => Some(1),
+            Effective::DoubleSuperEffective => Some(2),
         }
     }
 }
 
 impl PokemonType {
+    pub const ALL: [PokemonType; 19] = [
+        PokemonType::Unknown,
+        PokemonType::Normal,
+        PokemonType::Fire,
+        PokemonType::Water,
+        PokemonType::Electric,
+        PokemonType::Grass,
+        PokemonType::Ice,
+        PokemonType::Fighting,
+        PokemonType::Poison,
+        PokemonType::Ground,
+        PokemonType::Flying,
+        PokemonType::Psychic,
+        PokemonType::Bug,
+        PokemonType::Rock,
+        PokemonType::Ghost,
+        PokemonType::Dragon,
+        PokemonType::Dark,
+        PokemonType::Steel,
+        PokemonType::Fairy,
+    ];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "Unknown",
+            Self::Normal => "Normal",
+            Self::Fire => "Fire",
+            Self::Water => "Water",
+            Self::Electric => "Electric",
+            Self::Grass => "Grass",
+            Self::Ice => "Ice",
+            Self::Fighting => "Fighting",
+            Self::Poison => "Poison",
+            Self::Ground => "Ground",
+            Self::Flying => "Flying",
+            Self::Psychic => "Psychic",
+            Self::Bug => "Bug",
+            Self::Rock => "Rock",
+            Self::Ghost => "Ghost",
+            Self::Dragon => "Dragon",
+            Self::Dark => "Dark",
+            Self::Steel => "Steel",
+            Self::Fairy => "Fairy",
+        }
+    }
+
     pub const fn effective(&self, target: Self, category: MoveCategory) -> Effective {
         match category {
             MoveCategory::Status => Effective::Ineffective,
@@ -182,36 +256,52 @@ impl PokemonType {
     }
 }
 
-impl Default for PokemonType {
-    fn default() -> Self {
-        Self::Unknown
+/// Combines the effectiveness against each of a pokemon's types by multiplying them, staying between 0.25x and 4x.
+/// Immunity always wins, so a type that is [Effective::Ineffective] makes the whole product ineffective,
+/// even if the other type is weak to the move (such as Ground against Flying/Steel).
+impl Mul for Effective {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        match (self.exponent(), rhs.exponent()) {
+            (Some(a), Some(b)) => match (a + b).clamp(-2, 2) {
+                -2 => Self::DoubleNotEffective,
+                -1 => Self::NotEffective,
+                0 => Self::Effective,
+                1 => Self::SuperEffective,
+                _ => Self::DoubleSuperEffective,
+            },
+            _ => Self::Ineffective,
+        }
     }
 }
 
-impl Default for Effective {
-    fn default() -> Self {
-        Self::Effective
+impl Display for PokemonType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.name())
     }
 }
 
-impl Mul for Effective {
-    type Output = Self;
+/// The error returned when a string is not the name of a [PokemonType].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownPokemonType(pub String);
 
-    fn mul(self, rhs: Self) -> Self::Output {
-        match self {
-            Self::Ineffective => Self::Ineffective,
-            Self::NotEffective => match rhs {
-                Self::SuperEffective => Self::Effective,
-                Self::Ineffective => Self::Ineffective,
-                _ => Self::NotEffective,
-            },
-            Self::Effective => rhs,
-            Self::SuperEffective => match rhs {
-                Self::NotEffective => Self::Effective,
-                Self::Ineffective => Self::Ineffective,
-                _ => Self::SuperEffective,
-            },
-        }
+impl Display for UnknownPokemonType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "\"{}\" is not a pokemon type", self.0)
+    }
+}
+
+/// Parses the name of a type, ignoring case.
+impl FromStr for PokemonType {
+    type Err = UnknownPokemonType;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|pokemon_type| pokemon_type.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| UnknownPokemonType(s.to_owned()))
     }
 }
 
@@ -222,10 +312,103 @@ impl Display for Effective {
             "{}",
             match self {
                 Effective::Ineffective => "ineffective",
-                Effective::NotEffective => "not very effective",
+                Effective::NotEffective | Effective::DoubleNotEffective => "not very effective",
                 Effective::Effective => "effective",
-                Effective::SuperEffective => "super effective",
+                Effective::SuperEffective | Effective::DoubleSuperEffective => "super effective",
             }
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Effective, PokemonType, UnknownPokemonType};
+    use crate::{
+        moves::MoveCategory,
+        testing::{pokedex, SKARMORY},
+    };
+
+    #[test]
+    fn immunity_dominates_dual_types() {
+        let ground = |defender: PokemonType| PokemonType::Ground.effective(defender, MoveCategory::Physical);
+        assert_eq!(ground(PokemonType::Flying), Effective::Ineffective);
+        assert_eq!(ground(PokemonType::Steel), Effective::SuperEffective);
+        assert_eq!(
+            ground(PokemonType::Flying) * ground(PokemonType::Steel),
+            Effective::Ineffective
+        );
+        assert_eq!(
+            Effective::DoubleSuperEffective * Effective::Ineffective,
+            Effective::Ineffective
+        );
+
+        let pokedex = pokedex();
+        let skarmory = pokedex.get(&SKARMORY);
+        assert_eq!(
+            skarmory.effective(PokemonType::Ground, MoveCategory::Physical),
+            Effective::Ineffective
+        );
+    }
+
+    #[test]
+    fn dual_type_products() {
+        assert_eq!(
+            Effective::SuperEffective * Effective::SuperEffective,
+            Effective::DoubleSuperEffective
+        );
+        assert_eq!(
+            Effective::SuperEffective * Effective::NotEffective,
+            Effective::Effective
+        );
+        assert_eq!(
+            Effective::NotEffective * Effective::NotEffective,
+            Effective::DoubleNotEffective
+        );
+    }
+
+    #[test]
+    fn effectiveness_multipliers() {
+        for (effective, multiplier) in [
+            (Effective::Ineffective, 0.0),
+            (Effective::DoubleNotEffective, 0.25),
+            (Effective::NotEffective, 0.5),
+            (Effective::Effective, 1.0),
+            (Effective::SuperEffective, 2.0),
+            (Effective::DoubleSuperEffective, 4.0),
+        ] {
+            assert!((effective.multiplier() - multiplier).abs() < f32::EPSILON);
+        }
+        // products stay between 0.25x and 4x
+        assert_eq!(
+            Effective::DoubleSuperEffective * Effective::SuperEffective,
+            Effective::DoubleSuperEffective
+        );
+        assert_eq!(
+            Effective::DoubleNotEffective * Effective::NotEffective,
+            Effective::DoubleNotEffective
+        );
+        // a single immunity is enough to make a dual type immune
+        assert_eq!(
+            PokemonType::Ground.effective(PokemonType::Flying, MoveCategory::Physical)
+                * PokemonType::Ground.effective(PokemonType::Ground, MoveCategory::Physical),
+            Effective::Ineffective
+        );
+    }
+
+    #[test]
+    fn parse_types() {
+        assert_eq!("ELECTRIC".parse(), Ok(PokemonType::Electric));
+        assert_eq!("electric".parse(), Ok(PokemonType::Electric));
+        assert_eq!(
+            "Light".parse::<PokemonType>(),
+            Err(UnknownPokemonType("Light".to_owned()))
+        );
+        assert_eq!(
+            UnknownPokemonType("Light".to_owned()).to_string(),
+            "\"Light\" is not a pokemon type"
+        );
+        for pokemon_type in PokemonType::ALL {
+            assert_eq!(pokemon_type.to_string().parse(), Ok(pokemon_type));
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    moves::MoveCategory,
+    types::{Effective, PokemonType},
+};
+
+/// How effective each type is against every other type.
+/// Serialized as a map of attacking types to maps of defending types.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct TypeChart(HashMap<PokemonType, HashMap<PokemonType, Effective>>);
+
+/// A problem found in a [TypeChart].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeChartIssue {
+    /// There is no effectiveness defined for this matchup, so it is treated as [Effective::Effective].
+    MissingMatchup {
+        attacker: PokemonType,
+        defender: PokemonType,
+    },
+}
+
+impl TypeChart {
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+
+    pub fn get(&self, attacker: PokemonType, defender: PokemonType) -> Effective {
+        self.0
+            .get(&attacker)
+            .and_then(|defenders| defenders.get(&defender))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn set(&mut self, attacker: PokemonType, defender: PokemonType, effective: Effective) {
+        self.0
+            .entry(attacker)
+            .or_default()
+            .insert(defender, effective);
+    }
+
+    pub fn effective(
+        &self,
+        attacker: PokemonType,
+        defender: PokemonType,
+        category: MoveCategory,
+    ) -> Effective {
+        match category {
+            MoveCategory::Status => Effective::Ineffective,
+            _ => self.get(attacker, defender),
+        }
+    }
+
+    /// Lists every matchup that is not defined in the chart.
+    /// Matchups are not compared with their reverse, since type effectiveness is not symmetric
+    /// (fire is super effective against grass, while grass is not very effective against fire).
+    pub fn validate(&self) -> Vec<TypeChartIssue> {
+        PokemonType::ALL
+            .iter()
+            .flat_map(|attacker| {
+                PokemonType::ALL
+                    .iter()
+                    .map(move |defender| (*attacker, *defender))
+            })
+            .filter(|(attacker, defender)| {
+                !self
+                    .0
+                    .get(attacker)
+                    .map(|defenders| defenders.contains_key(defender))
+                    .unwrap_or_default()
+            })
+            .map(|(attacker, defender)| TypeChartIssue::MissingMatchup { attacker, defender })
+            .collect()
+    }
+}
+
+/// The standard type chart.
+impl Default for TypeChart {
+    fn default() -> Self {
+        let mut chart = Self::empty();
+        for attacker in PokemonType::ALL {
+            for defender in PokemonType::ALL {
+                chart.set(
+                    attacker,
+                    defender,
+                    attacker.effective(defender, MoveCategory::Physical),
+                );
+            }
+        }
+        chart
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TypeChart, TypeChartIssue};
+    use crate::{
+        moves::MoveCategory,
+        types::{Effective, PokemonType},
+    };
+
+    #[test]
+    fn default_chart_matches_type_effectiveness() {
+        let chart = TypeChart::default();
+        assert!(chart.validate().is_empty());
+        for attacker in PokemonType::ALL {
+            for defender in PokemonType::ALL {
+                assert_eq!(
+                    chart.effective(attacker, defender, MoveCategory::Special),
+                    attacker.effective(defender, MoveCategory::Special)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn custom_chart_from_data() {
+        let chart: TypeChart =
+            serde_json::from_str(r#"{ "Normal": { "Ghost": "Effective", "Rock": "NotEffective" } }"#).unwrap();
+        assert_eq!(
+            TypeChart::default().effective(PokemonType::Normal, PokemonType::Ghost, MoveCategory::Physical),
+            Effective::Ineffective
+        );
+        assert_eq!(
+            chart.effective(PokemonType::Normal, PokemonType::Ghost, MoveCategory::Physical),
+            Effective::Effective
+        );
+        assert_eq!(
+            chart.effective(PokemonType::Normal, PokemonType::Ghost, MoveCategory::Status),
+            Effective::Ineffective
+        );
+        assert!(chart.validate().contains(&TypeChartIssue::MissingMatchup {
+            attacker: PokemonType::Fire,
+            defender: PokemonType::Water,
+        }));
+    }
+
+    #[test]
+    fn chart_round_trip() {
+        let mut chart = TypeChart::default();
+        chart.set(PokemonType::Normal, PokemonType::Ghost, Effective::Effective);
+        let json = serde_json::to_string(&chart).unwrap();
+        assert_eq!(serde_json::from_str::<TypeChart>(&json).unwrap(), chart);
+    }
+
+    #[test]
+    fn set_matchup() {
+        let mut chart = TypeChart::empty();
+        assert_eq!(chart.validate().len(), PokemonType::ALL.len() * PokemonType::ALL.len());
+        // undefined matchups are neutral
+        assert_eq!(chart.get(PokemonType::Fire, PokemonType::Grass), Effective::Effective);
+
+        chart.set(PokemonType::Fire, PokemonType::Grass, Effective::DoubleSuperEffective);
+        assert_eq!(chart.get(PokemonType::Fire, PokemonType::Grass), Effective::DoubleSuperEffective);
+        // matchups are not reciprocal
+        assert_eq!(chart.get(PokemonType::Grass, PokemonType::Fire), Effective::Effective);
+        assert!(!chart.validate().contains(&TypeChartIssue::MissingMatchup {
+            attacker: PokemonType::Fire,
+            defender: PokemonType::Grass,
+        }));
+
+        chart.set(PokemonType::Fire, PokemonType::Grass, Effective::NotEffective);
+        assert_eq!(chart.get(PokemonType::Fire, PokemonType::Grass), Effective::NotEffective);
+    }
+}